@@ -1,6 +1,4 @@
-use std::{path::Path};
-
-mod stock;
+use stockwatcher::stock;
 // mod json;
 
 fn main() {
@@ -32,7 +30,7 @@ fn main() {
     // println!("macds {:?}\n\n", macds);
 
     // let prices: Vec<f32> = data.iter().map(|el| el.get_close()).collect();
-    // let rsis = stock::ta::rsi::run(prices);
+    // let rsis = stock::ta::rsi::run(prices, 14);
     // println!("rsis {:?}\n\n", rsis);
 
     // let prices: Vec<f32> = data.iter().map(|el| el.get_close()).collect();
@@ -41,7 +39,7 @@ fn main() {
     // println!("obvs {:?}\n\n", obvs);
 
     // let prices: Vec<(f32, f32, f32)> = data.iter().map(|el| (el.get_close(), el.get_low(), el.get_high())).collect();
-    // let stoch_osc = stock::ta::stochastic_oscillator::run(prices);
+    // let stoch_osc = stock::ta::stochastic_oscillator::run(prices, 14);
     // println!("stochastic_oscillator {:?}\n\n", stoch_osc);
 
     // let prices: Vec<f32> = data.iter().map(|el| el.get_close()).collect();
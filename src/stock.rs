@@ -2,10 +2,19 @@ use std::{path::Path, fs::{read_to_string}};
 
 pub mod historical;
 pub mod backtest;
+pub mod cache;
+pub mod data;
 pub mod datetime;
+pub mod io;
+pub mod report;
+pub mod strategy;
 pub mod ta;
+#[cfg(any(test, feature = "testutil"))]
+pub mod testutil;
 pub mod iex;
 
+pub use self::ta::InputPolicy;
+
 #[derive(Debug)]
 pub struct Stock {
     ticker: String,
@@ -61,6 +70,16 @@ impl Stock {
 
     pub fn get_historical_data(&self) -> &Vec<historical::HistoricalData> {return &self.historical_data;}
     pub fn load_historical_data(&mut self, location: &Path) -> Result<bool, String> {
+        return self.load_historical_data_with(location, true);
+    }
+
+    /// Load historical data like `load_historical_data`, optionally skipping
+    /// the check (see `io::validate_series`) that the rows are in ascending
+    /// date order with no two rows sharing a date.
+    ///
+    /// NOTE: The rows are only added to the stock (and sorted by date) once
+    /// they pass validation, so on `Err` the stock is left unchanged.
+    pub fn load_historical_data_with(&mut self, location: &Path, validate: bool) -> Result<bool, String> {
         if !location.exists() {
             return Err(format!("Could not find location for historical data for ticker {}: {:?}", self.ticker, location));
         }
@@ -74,9 +93,10 @@ impl Stock {
         // Add HistoricalData to Stock
         let content = content.replace("\r", "");
         let contents: Vec<&str> = content.split('\n').collect();
+        let mut rows: Vec<historical::HistoricalData> = Vec::new();
         for line in &contents[1..contents.len()] {
             let values: Vec<&str> = line.split(',').collect();
-            self.historical_data.push(historical::HistoricalData::new(
+            rows.push(historical::HistoricalData::new(
                 values[0],
                 match values[1].to_string().parse() {
                     Ok(open) => open,
@@ -101,7 +121,17 @@ impl Stock {
             ));
         }
 
+        // Validate the rows in the order they were read
+        if validate {
+            let timestamps: Vec<i64> = rows.iter().map(|d| d.get_date().timestamp()).collect();
+            match io::validate_series(&timestamps) {
+                Ok(_) => {},
+                Err(error) => return Err(format!("Invalid historical data for ticker {}: {}", self.ticker, error)),
+            };
+        }
+
         // Sort data
+        self.historical_data.extend(rows);
         historical::sort_by_date(&mut self.historical_data);
 
         return Ok(true);
@@ -161,10 +191,12 @@ impl Stock {
     pub fn backtest(&self) {
         backtest::run(self);
     }
+}
 
-    pub fn to_string(&self) -> String {
+impl std::fmt::Display for Stock {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut output = "".to_owned();
-        output.push_str("{",);
+        output.push('{');
         output.push_str(&format!("\"ticker\": \"{}\",", self.ticker));
         output.push_str(&format!("\"security_type\": \"{}\",", self.security_type));
         output.push_str(&format!("\"name\": \"{}\",", self.name));
@@ -175,12 +207,12 @@ impl Stock {
         output.push_str(&format!("\"low52\": {},", self.low52));
         output.push_str("\"historical_data\": [");
         for d in self.historical_data.iter() {
-            output.push_str(&format!("{},", d.to_string()));
+            output.push_str(&format!("{},", d));
         }
         // Remove the last comma
         output.pop();
         output.push_str("]}");
-        return output;
+        return write!(f, "{}", output);
     }
 }
 
@@ -221,4 +253,40 @@ mod tests {
         assert_eq!(hd[0].get_close(), 182.92);
         assert_eq!(hd[0].get_volume(), 119636836);
     }
+
+    #[test]
+    fn test_load_historical_data_pre_epoch() {
+        let mut s= Stock::new("ko");
+        match s.load_historical_data(Path::new("./test/data/ko_historical.csv")) {Ok(b) => b, Err(error) => panic!("{}", error)};
+        let hd = s.get_historical_data();
+        assert_eq!(hd.len(), 5);
+        assert_eq!(hd[0].get_date().to_string(), "1/2/1962 16:00:00");
+        assert_eq!(hd[4].get_date().to_string(), "1/8/1962 16:00:00");
+    }
+
+    #[test]
+    fn test_load_historical_data_out_of_order() {
+        let mut s= Stock::new("spy");
+        let location = Path::new("./test/data/spy_historical_unsorted.csv");
+        assert_eq!(s.load_historical_data(location), Err("Invalid historical data for ticker spy: Timestamps out of order. Entry 2 comes before the entry preceding it.".to_owned()));
+        assert_eq!(s.get_historical_data().len(), 0);
+
+        match s.load_historical_data_with(location, false) {Ok(b) => b, Err(error) => panic!("{}", error)};
+        let hd = s.get_historical_data();
+        assert_eq!(hd.len(), 4);
+        assert_eq!(hd[0].get_date().to_string(), "12/31/2013 16:00:00");
+        assert_eq!(hd[3].get_date().to_string(), "2/3/2014 16:00:00");
+    }
+
+    #[test]
+    fn test_load_historical_data_duplicates() {
+        let mut s= Stock::new("spy");
+        let location = Path::new("./test/data/spy_historical_duplicates.csv");
+        assert_eq!(s.load_historical_data(location), Err("Invalid historical data for ticker spy: Duplicate timestamp. Entry 2 is equal to the entry preceding it.".to_owned()));
+        assert_eq!(s.get_historical_data().len(), 0);
+
+        let mut s= Stock::new("spy");
+        match s.load_historical_data_with(location, false) {Ok(b) => b, Err(error) => panic!("{}", error)};
+        assert_eq!(s.get_historical_data().len(), 4);
+    }
 }
\ No newline at end of file
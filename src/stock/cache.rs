@@ -0,0 +1,119 @@
+use std::collections::HashMap;
+
+/// Identifies a single indicator calculation: which indicator, with which
+/// parameters, on which input.
+#[derive(Debug)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct CacheKey {
+    indicator: String,
+    /// Bit patterns of the parameters so they can be hashed.
+    params: Vec<u32>,
+    input_len: usize,
+    input_hash: u64,
+}
+
+impl CacheKey {
+    /// ### Example
+    /// ```
+    /// CacheKey::new("rsi", &[14.0], &prices);
+    /// ```
+    pub fn new(indicator: &str, params: &[f32], input: &[f32]) -> Self {
+        return Self {
+            indicator: indicator.to_owned(),
+            params: params.iter().map(|p| p.to_bits()).collect(),
+            input_len: input.len(),
+            input_hash: hash(input),
+        };
+    }
+}
+
+/// Memoizes indicator outputs so recalculating an indicator on unchanged
+/// data (e.g. on every dashboard refresh) is free.
+///
+/// NOTE: Inputs are identified by a 64-bit FNV-1a hash of their values and
+/// their length rather than a full copy, so two different inputs colliding
+/// is possible but extremely unlikely.
+///
+/// ### Example
+/// ```
+/// let mut cache = IndicatorCache::new();
+/// let key = CacheKey::new("rsi", &[14.0], &prices);
+/// cache.get_or_compute(key, || rsi::run(prices.to_vec(), 14));
+/// ```
+pub struct IndicatorCache {
+    entries: HashMap<CacheKey, Vec<f32>>,
+}
+
+impl Default for IndicatorCache {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl IndicatorCache {
+    pub fn new() -> Self {
+        return Self { entries: HashMap::new() };
+    }
+
+    /// Return the cached output for `key`, calculating and caching it with
+    /// `compute` if it is not cached yet.
+    pub fn get_or_compute<F: FnOnce() -> Vec<f32>>(&mut self, key: CacheKey, compute: F) -> &[f32] {
+        return self.entries.entry(key).or_insert_with(compute);
+    }
+
+    /// Number of cached outputs.
+    pub fn len(&self) -> usize { return self.entries.len(); }
+
+    /// Whether no outputs are cached.
+    pub fn is_empty(&self) -> bool { return self.entries.is_empty(); }
+
+    /// Remove every cached output.
+    pub fn clear(&mut self) { self.entries.clear(); }
+}
+
+/// 64-bit FNV-1a hash of the bit patterns of a `&[f32]`.
+fn hash(values: &[f32]) -> u64 {
+    let mut h: u64 = 0xcbf29ce484222325;
+    for v in values {
+        for b in v.to_bits().to_le_bytes() {
+            h ^= b as u64;
+            h = h.wrapping_mul(0x100000001b3);
+        }
+    }
+    return h;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+    use crate::stock::ta::rsi;
+
+    #[test]
+    fn test_get_or_compute_cached() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let calls = Cell::new(0);
+        let mut cache = IndicatorCache::new();
+        let compute = || {
+            calls.set(calls.get() + 1);
+            return rsi::run(prices.to_vec(), 3);
+        };
+        let first = cache.get_or_compute(CacheKey::new("rsi", &[3.0], &prices), compute).to_vec();
+        let second = cache.get_or_compute(CacheKey::new("rsi", &[3.0], &prices), compute).to_vec();
+        assert_eq!(first, second);
+        assert_eq!(calls.get(), 1);
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn test_get_or_compute_different_keys() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let mut changed = prices.to_vec();
+        changed[7] = 2.0;
+        let mut cache = IndicatorCache::new();
+        cache.get_or_compute(CacheKey::new("rsi", &[3.0], &prices), || rsi::run(prices.to_vec(), 3));
+        cache.get_or_compute(CacheKey::new("rsi", &[4.0], &prices), || rsi::run(prices.to_vec(), 4));
+        cache.get_or_compute(CacheKey::new("rsi", &[3.0], &changed), || rsi::run(changed.to_vec(), 3));
+        assert_eq!(cache.len(), 3);
+        cache.clear();
+        assert_eq!(cache.len(), 0);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::stock::historical::Candle;
+
+/// Column store of OHLCV bars: each field in its own contiguous `Vec`
+/// (struct-of-arrays) rather than a `Vec<Candle>`.
+///
+/// Indicators only ever read one or two fields, so on large series keeping
+/// each column contiguous touches far less memory than striding over whole
+/// candles, and the columns can be passed to the indicators as `&[f32]`
+/// without first projecting them out (see `ta::closes`).
+///
+/// ### Example
+/// ```
+/// let frame = CandleFrame::from_candles(stock.get_historical_data());
+/// rsi::run_iter(frame.closes(), 14).collect::<Vec<f32>>();
+/// ```
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct CandleFrame {
+    opens: Vec<f32>,
+    highs: Vec<f32>,
+    lows: Vec<f32>,
+    closes: Vec<f32>,
+    volumes: Vec<u64>,
+}
+
+impl Default for CandleFrame {
+    fn default() -> Self { return Self::new(); }
+}
+
+impl CandleFrame {
+    pub fn new() -> Self {
+        return Self {
+            opens: Vec::new(),
+            highs: Vec::new(),
+            lows: Vec::new(),
+            closes: Vec::new(),
+            volumes: Vec::new(),
+        };
+    }
+
+    /// Split a `&[Candle]` into its columns.
+    pub fn from_candles(candles: &[Candle]) -> Self {
+        let mut frame = Self::new();
+        for c in candles {
+            frame.push(c.get_open(), c.get_high(), c.get_low(), c.get_close(), c.get_volume() as u64);
+        }
+        return frame;
+    }
+
+    /// Append a bar to the end of every column.
+    pub fn push(&mut self, open: f32, high: f32, low: f32, close: f32, volume: u64) {
+        self.opens.push(open);
+        self.highs.push(high);
+        self.lows.push(low);
+        self.closes.push(close);
+        self.volumes.push(volume);
+    }
+
+    pub fn len(&self) -> usize { return self.closes.len(); }
+    pub fn is_empty(&self) -> bool { return self.closes.is_empty(); }
+
+    pub fn opens(&self) -> &[f32] { return &self.opens; }
+    pub fn highs(&self) -> &[f32] { return &self.highs; }
+    pub fn lows(&self) -> &[f32] { return &self.lows; }
+    pub fn closes(&self) -> &[f32] { return &self.closes; }
+    pub fn volumes(&self) -> &[u64] { return &self.volumes; }
+
+    /// Zip the columns into `(close, low, high)` tuples, the layout the
+    /// bar-based indicators (e.g. `stochastic_oscillator::run`) expect.
+    pub fn close_low_highs(&self) -> Vec<(f32, f32, f32)> {
+        let mut bars: Vec<(f32, f32, f32)> = Vec::new();
+        for i in 0..self.len() {
+            bars.push((self.closes[i], self.lows[i], self.highs[i]));
+        }
+        return bars;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::historical::HistoricalData;
+    use crate::stock::ta::{extract, rsi, stochastic_oscillator, vwap};
+
+    fn candles() -> Vec<Candle> {
+        let closes = vec![10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0];
+        return closes.iter().enumerate().map(|(i, &c)| {
+            HistoricalData::new("4/23/2021 16:00:00", c - 0.5, c + 1.0, c - 1.0, c, 1000 + 10 * i as u32)
+        }).collect();
+    }
+
+    #[test]
+    fn test_from_candles() {
+        let candles = candles();
+        let frame = CandleFrame::from_candles(&candles);
+        assert_eq!(frame.len(), candles.len());
+        assert_eq!(frame.is_empty(), false);
+        assert_eq!(frame.opens().to_vec(), extract::opens(&candles));
+        assert_eq!(frame.highs().to_vec(), extract::highs(&candles));
+        assert_eq!(frame.lows().to_vec(), extract::lows(&candles));
+        assert_eq!(frame.closes().to_vec(), extract::closes(&candles));
+        assert_eq!(frame.volumes()[3], 1030);
+        assert_eq!(frame.close_low_highs(), extract::close_low_highs(&candles));
+    }
+
+    #[test]
+    fn test_rsi_on_close_column() {
+        let candles = candles();
+        let frame = CandleFrame::from_candles(&candles);
+        let rsis: Vec<f32> = rsi::run_iter(frame.closes(), 14).collect();
+        assert_eq!(rsis, rsi::run(extract::closes(&candles), 14));
+        assert_eq!(rsis, vec![57.69231, 49.492382]);
+    }
+
+    #[test]
+    fn test_bar_and_volume_indicators() {
+        let candles = candles();
+        let frame = CandleFrame::from_candles(&candles);
+        let bars = frame.close_low_highs();
+        assert_eq!(stochastic_oscillator::run(bars.to_vec(), 14), stochastic_oscillator::run_candles(&candles, 14));
+        assert_eq!(vwap::run(&bars, frame.volumes()).len(), frame.len());
+    }
+
+    #[test]
+    fn test_push() {
+        let mut frame = CandleFrame::new();
+        assert_eq!(frame.is_empty(), true);
+        frame.push(10.0, 11.0, 9.0, 10.5, 500);
+        assert_eq!(frame.len(), 1);
+        assert_eq!(frame.closes(), &[10.5]);
+        assert_eq!(frame.close_low_highs(), vec![(10.5, 9.0, 11.0)]);
+    }
+}
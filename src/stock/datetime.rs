@@ -86,13 +86,12 @@ impl DateTime {
     /// assert_eq!(d2.is_after(&d1), false);
     /// ```
     pub fn is_after(&self, d: &DateTime) -> bool {
-        if self.second > d.get_second() { return true; }
-        if self.minute > d.get_minute() { return true; }
-        if self.hour > d.get_hour() { return true; }
-        if self.day > d.get_day() { return true; }
-        if self.month > d.get_month() { return true; }
-        if self.year > d.get_year() { return true; }
-        return false;
+        if self.year != d.get_year() { return self.year > d.get_year(); }
+        if self.month != d.get_month() { return self.month > d.get_month(); }
+        if self.day != d.get_day() { return self.day > d.get_day(); }
+        if self.hour != d.get_hour() { return self.hour > d.get_hour(); }
+        if self.minute != d.get_minute() { return self.minute > d.get_minute(); }
+        return self.second > d.get_second();
     }
 
     /// Compares two `DateTime`s and returns `true` if the caller is on
@@ -121,13 +120,12 @@ impl DateTime {
     /// assert_eq!(d2.is_before(&d1), false);
     /// ```
     pub fn is_before(&self, d: &DateTime) -> bool {
-        if self.second < d.get_second() { return true; }
-        if self.minute < d.get_minute() { return true; }
-        if self.hour < d.get_hour() { return true; }
-        if self.day < d.get_day() { return true; }
-        if self.month < d.get_month() { return true; }
-        if self.year < d.get_year() { return true; }
-        return false;
+        if self.year != d.get_year() { return self.year < d.get_year(); }
+        if self.month != d.get_month() { return self.month < d.get_month(); }
+        if self.day != d.get_day() { return self.day < d.get_day(); }
+        if self.hour != d.get_hour() { return self.hour < d.get_hour(); }
+        if self.minute != d.get_minute() { return self.minute < d.get_minute(); }
+        return self.second < d.get_second();
     }
 
     /// Compares two `DateTime`s and returns `true` if the caller is on
@@ -146,11 +144,34 @@ impl DateTime {
         return false;
     }
 
-    pub fn to_string(&self) -> String {
+    /// Seconds since the Unix epoch (`1/1/1970 00:00:00`), treating the
+    /// `DateTime` as UTC. Dates before the epoch are negative.
+    ///
+    /// ```
+    /// let d = DateTime::new("1/2/1970 00:00:10");
+    /// assert_eq!(d.timestamp(), 86410);
+    /// ```
+    pub fn timestamp(&self) -> i64 {
+        // Days from the civil date, with years starting in March so the
+        // leap day falls at the end of the year
+        let y = if self.month <= 2 { self.year as i64 - 1 } else { self.year as i64 };
+        let m = self.month as i64;
+        let era = y.div_euclid(400);
+        let yoe = y - era * 400;
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + self.day as i64 - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        let days = era * 146097 + doe - 719468;
+        let secs = days * 86400 + self.hour as i64 * 3600 + self.minute as i64 * 60 + self.second as i64;
+        return secs;
+    }
+}
+
+impl std::fmt::Display for DateTime {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let hr = if self.hour < 10 { format!("0{}", self.hour) } else { format!("{}", self.hour) };
         let min = if self.minute < 10 { format!("0{}", self.minute) } else { format!("{}", self.minute) };
         let sec = if self.second < 10 { format!("0{}", self.second) } else { format!("{}", self.second) };
-        return format!("{}/{}/{} {}:{}:{}", self.month, self.day, self.year, hr, min, sec);
+        return write!(f, "{}/{}/{} {}:{}:{}", self.month, self.day, self.year, hr, min, sec);
     }
 }
 
@@ -245,4 +266,25 @@ mod tests {
         assert_eq!(d1.is_before(&d2), false);
         assert_eq!(d1.is_on_or_before(&d2), false);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_comparisons_across_fields() {
+        // A later day in an earlier month is still before
+        let d1 = DateTime::new("1/31/2021 16:00:00");
+        let d2 = DateTime::new("2/1/2021 09:30:00");
+        assert_eq!(d1.is_before(&d2), true);
+        assert_eq!(d1.is_after(&d2), false);
+        assert_eq!(d2.is_before(&d1), false);
+        assert_eq!(d2.is_after(&d1), true);
+    }
+
+    #[test]
+    fn test_timestamp() {
+        assert_eq!(DateTime::new("1/1/1970 00:00:00").timestamp(), 0);
+        assert_eq!(DateTime::new("1/2/1970 00:00:10").timestamp(), 86410);
+        assert_eq!(DateTime::new("1/2/2014 16:00:00").timestamp(), 1388678400);
+        assert_eq!(DateTime::new("2/29/2020 12:30:00").timestamp(), 1582979400);
+        assert_eq!(DateTime::new("12/31/1969 23:59:59").timestamp(), -1);
+        assert_eq!(DateTime::new("1/2/1962 16:00:00").timestamp(), -252316800);
+    }
+}
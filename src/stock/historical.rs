@@ -41,33 +41,38 @@ impl HistoricalData {
 
     pub fn get_volume(&self) -> u32 { return self.volume; }
     pub fn set_volume(&mut self, volume: u32) { self.volume = volume; }
+}
 
-    pub fn to_string(&self) -> String {
+impl std::fmt::Display for HistoricalData {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut output = "".to_owned();
-        output.push_str("{",);
-        output.push_str(&format!("\"date\": \"{}\",", self.get_date().to_string()));
+        output.push('{');
+        output.push_str(&format!("\"date\": \"{}\",", self.get_date()));
         output.push_str(&format!("\"close\": {},", self.get_close()));
         output.push_str(&format!("\"high\": {},", self.get_high()));
         output.push_str(&format!("\"low\": {},", self.get_low()));
         output.push_str(&format!("\"open\": {},", self.get_open()));
         output.push_str(&format!("\"volume\": {}", self.get_volume()));
-        output.push_str("}");
-        return output;
+        output.push('}');
+        return write!(f, "{}", output);
     }
 }
 
+/// A single OHLCV bar, as consumed by the `ta` helpers.
+pub type Candle = HistoricalData;
+
 pub fn sort_by_date(d: &mut Vec<HistoricalData>) {
     quicksort(d);
 }
 
-fn partition(a: &mut Vec<HistoricalData>, low: i64, high: i64) -> i64 {
+fn partition(a: &mut [HistoricalData], low: i64, high: i64) -> i64 {
     let pivot = match a.get(high as usize) {
         Some(el) => el.clone(),
         _ => panic!("Array index {} out of bounds.", high-1)
     };
     let mut i = low - 1;
     for j in low..high {
-        if a[j as usize].get_date().is_before(&pivot.get_date()) {
+        if a[j as usize].get_date().is_before(pivot.get_date()) {
             i += 1;
             a.swap(i as usize, j as usize);
         }
@@ -119,10 +124,10 @@ mod tests {
         let d5 = HistoricalData::new("4/23/2017 16:00:00", 10.0, 17.0, 8.0, 11.0, 10050);
         let mut dates = vec![d1,d2,d3,d4,d5];
         sort_by_date(&mut dates);
-        assert_eq!(dates[0].get_date().is_before(&dates[1].get_date()), true);
-        assert_eq!(dates[1].get_date().is_before(&dates[2].get_date()), true);
-        assert_eq!(dates[2].get_date().is_before(&dates[3].get_date()), true);
-        assert_eq!(dates[3].get_date().is_before(&dates[4].get_date()), true);
+        assert_eq!(dates[0].get_date().is_before(dates[1].get_date()), true);
+        assert_eq!(dates[1].get_date().is_before(dates[2].get_date()), true);
+        assert_eq!(dates[2].get_date().is_before(dates[3].get_date()), true);
+        assert_eq!(dates[3].get_date().is_before(dates[4].get_date()), true);
     }
 
     #[test]
@@ -134,10 +139,10 @@ mod tests {
         let d5 = HistoricalData::new("4/23/2020 16:00:00", 10.0, 17.0, 8.0, 11.0, 10050);
         let mut dates = vec![d1,d2,d3,d4,d5];
         sort_by_date(&mut dates);
-        assert_eq!(dates[0].get_date().is_before(&dates[1].get_date()), true);
-        assert_eq!(dates[1].get_date().is_before(&dates[2].get_date()), true);
-        assert_eq!(dates[2].get_date().is_before(&dates[3].get_date()), true);
-        assert_eq!(dates[3].get_date().is_before(&dates[4].get_date()), true);
+        assert_eq!(dates[0].get_date().is_before(dates[1].get_date()), true);
+        assert_eq!(dates[1].get_date().is_before(dates[2].get_date()), true);
+        assert_eq!(dates[2].get_date().is_before(dates[3].get_date()), true);
+        assert_eq!(dates[3].get_date().is_before(dates[4].get_date()), true);
     }
 
     #[test]
@@ -149,10 +154,10 @@ mod tests {
         let d5 = HistoricalData::new("4/23/2020 16:30:10", 10.0, 17.0, 8.0, 11.0, 10050);
         let mut dates = vec![d1,d2,d3,d4,d5];
         sort_by_date(&mut dates);
-        assert_eq!(dates[0].get_date().is_before(&dates[1].get_date()), true);
-        assert_eq!(dates[1].get_date().is_before(&dates[2].get_date()), true);
-        assert_eq!(dates[2].get_date().is_before(&dates[3].get_date()), true);
-        assert_eq!(dates[3].get_date().is_before(&dates[4].get_date()), true);
+        assert_eq!(dates[0].get_date().is_before(dates[1].get_date()), true);
+        assert_eq!(dates[1].get_date().is_before(dates[2].get_date()), true);
+        assert_eq!(dates[2].get_date().is_before(dates[3].get_date()), true);
+        assert_eq!(dates[3].get_date().is_before(dates[4].get_date()), true);
     }
 
     #[test]
@@ -164,9 +169,9 @@ mod tests {
         let d5 = HistoricalData::new("4/23/2020 16:30:20", 10.0, 17.0, 8.0, 11.0, 10050);
         let mut dates = vec![d1,d2,d3,d4,d5];
         sort_by_date(&mut dates);
-        assert_eq!(dates[0].get_date().is_before(&dates[1].get_date()), true);
-        assert_eq!(dates[1].get_date().is_before(&dates[2].get_date()), true);
-        assert_eq!(dates[2].get_date().is_before(&dates[3].get_date()), true);
-        assert_eq!(dates[3].get_date().is_before(&dates[4].get_date()), true);
+        assert_eq!(dates[0].get_date().is_before(dates[1].get_date()), true);
+        assert_eq!(dates[1].get_date().is_before(dates[2].get_date()), true);
+        assert_eq!(dates[2].get_date().is_before(dates[3].get_date()), true);
+        assert_eq!(dates[3].get_date().is_before(dates[4].get_date()), true);
     }
 }
\ No newline at end of file
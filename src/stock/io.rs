@@ -0,0 +1,341 @@
+use std::io::BufRead;
+
+use crate::stock::ta::rsi::RsiState;
+use crate::stock::InputPolicy;
+use crate::stock::ta::util::{self, TaError};
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Gap {
+    /// Index of the last timestamp before the gap.
+    pub after_index: usize,
+    /// Number of expected timestamps missing from the gap.
+    pub missing: u64,
+}
+
+/// Problem found by `validate_series` in a series of timestamps.
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub enum SeriesError {
+    /// Timestamp at `index` comes before the one preceding it.
+    OutOfOrder { index: usize },
+    /// Timestamp at `index` is equal to the one preceding it.
+    Duplicate { index: usize },
+}
+
+impl std::fmt::Display for SeriesError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            SeriesError::OutOfOrder { index } => write!(f, "Timestamps out of order. Entry {} comes before the entry preceding it.", index),
+            SeriesError::Duplicate { index } => write!(f, "Duplicate timestamp. Entry {} is equal to the entry preceding it.", index),
+        }
+    }
+}
+
+/// Problem reading a line of a price stream.
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub enum IoError {
+    /// The underlying reader failed.
+    Read(String),
+    /// Line `line` (1-based) is not a price.
+    Parse { line: usize, text: String },
+    /// Line `line` (1-based) was rejected by the `InputPolicy`.
+    Invalid { line: usize, error: TaError },
+}
+
+impl std::fmt::Display for IoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            IoError::Read(error) => write!(f, "Could not read from stream: {}", error),
+            IoError::Parse { line, text } => write!(f, "Could not parse line {} as a price. Received {:?}.", line, text),
+            IoError::Invalid { line, error } => write!(f, "Invalid price on line {}. {}", line, error),
+        }
+    }
+}
+
+/// Aggregate timestamped OHLCV bars into fixed-size time buckets.
+///
+/// Each bar is assigned to the bucket starting at `timestamp - (timestamp % bucket_secs)`
+/// and the bars within a bucket are combined as:
+/// - `open`: open of the first bar
+/// - `high`: highest high
+/// - `low`: lowest low
+/// - `close`: close of the last bar
+/// - `volume`: sum of the volumes
+///
+/// Buckets without any bars are skipped rather than filled.
+///
+/// NOTE: `bars` must be sorted by timestamp.
+///
+/// # Arguments
+/// * `bars` - `&[(u64, f32, f32, f32, f32, u64)]` in the format of
+///   `(timestamp, open, high, low, close, volume)` with the timestamp in seconds
+/// * `bucket_secs` - Size of each bucket in seconds
+///
+/// # Returns
+/// `Vec<(u64, f32, f32, f32, f32, u64)>` in the same format as `bars` where
+/// the timestamp is the start of the bucket.
+///
+/// ### Example
+/// ```
+/// // 1-minute bars into hourly bars
+/// io::resample(&bars, 60 * 60);
+/// ```
+pub fn resample(bars: &[(u64, f32, f32, f32, f32, u64)], bucket_secs: u64) -> Vec<(u64, f32, f32, f32, f32, u64)> {
+    if bucket_secs == 0 { panic!("Invalid bucket size. Received 0, but required at least 1 second."); }
+    let mut buckets: Vec<(u64, f32, f32, f32, f32, u64)> = Vec::new();
+    for &(ts, open, high, low, close, volume) in bars {
+        let start = ts - (ts % bucket_secs);
+        match buckets.last_mut() {
+            Some(b) if b.0 == start => {
+                if high > b.2 { b.2 = high; }
+                if low < b.3 { b.3 = low; }
+                b.4 = close;
+                b.5 += volume;
+            },
+            _ => buckets.push((start, open, high, low, close, volume)),
+        };
+    }
+    return buckets;
+}
+
+/// Find gaps in a series of timestamps where the spacing between two
+/// consecutive timestamps exceeds the expected interval.
+///
+/// Missing data distorts recursive indicators (e.g. Wilder's smoothing in
+/// the RSI), so callers can use this to decide whether to fill the gaps or
+/// bail out before running any indicators.
+///
+/// NOTE: `missing` is rounded up, so a spacing of 1.5 intervals reports a
+/// single missing timestamp.
+///
+/// # Arguments
+/// * `timestamps` - `&[u64]` containing sorted timestamps in seconds
+/// * `expected_interval` - Expected spacing between timestamps in seconds
+///
+/// ### Example
+/// ```
+/// // Daily bars
+/// io::find_gaps(&timestamps, 24 * 60 * 60);
+/// ```
+pub fn find_gaps(timestamps: &[u64], expected_interval: u64) -> Vec<Gap> {
+    if expected_interval == 0 { panic!("Invalid expected interval. Received 0, but required at least 1 second."); }
+    let mut gaps: Vec<Gap> = Vec::new();
+    for i in 1..timestamps.len() {
+        let spacing = timestamps[i].saturating_sub(timestamps[i-1]);
+        if spacing > expected_interval {
+            let missing = spacing.div_ceil(expected_interval) - 1;
+            gaps.push(Gap { after_index: i-1, missing: missing });
+        }
+    }
+    return gaps;
+}
+
+/// Check that a series of timestamps is strictly increasing.
+///
+/// Every indicator assumes its input is in chronological order with one
+/// entry per bar. Out-of-order or duplicate rows do not cause an error on
+/// their own, the indicators are just silently wrong, so validate the data
+/// before running any of them.
+///
+/// # Arguments
+/// * `timestamps` - `&[T]` containing timestamps (e.g. `u64` or `i64` seconds)
+///
+/// # Returns
+/// The first problem found, if any.
+///
+/// ### Example
+/// ```
+/// io::validate_series(&timestamps)?;
+/// ```
+pub fn validate_series<T: PartialOrd>(timestamps: &[T]) -> Result<(), SeriesError> {
+    for i in 1..timestamps.len() {
+        if timestamps[i] < timestamps[i-1] { return Err(SeriesError::OutOfOrder { index: i }); }
+        if timestamps[i] == timestamps[i-1] { return Err(SeriesError::Duplicate { index: i }); }
+    }
+    return Ok(());
+}
+
+/// Calculate the RSI of a stream of prices with one price per line, lazily.
+///
+/// Each line is parsed and fed to an `RsiState` as it is read, so a live
+/// feed (e.g. stdin) can be piped through without buffering the whole input.
+/// Nothing is emitted for the first `period` prices (the RSI's warm-up).
+///
+/// NOTE: A malformed line yields an `Err` and is otherwise skipped: the
+/// RSI continues from the previous valid price. Blank lines are ignored.
+///
+/// # Arguments
+/// * `reader` - `impl BufRead` with one price per line
+/// * `period` - Number of price changes to average (commonly 14)
+///
+/// ### Example
+/// ```
+/// for rsi in io::stream_rsi(std::io::stdin().lock(), 14) { ... }
+/// ```
+pub fn stream_rsi(reader: impl BufRead, period: usize) -> impl Iterator<Item = Result<f32, IoError>> {
+    return stream_rsi_inner(reader, period, None);
+}
+
+/// Calculate the RSI of a stream of prices, applying `policy` to zero or
+/// negative prices (see `stream_rsi`).
+///
+/// NOTE: A price rejected by `InputPolicy::Strict` yields an
+/// `IoError::Invalid` and is skipped like a malformed line. Its
+/// `TaError::NonPositive` index counts the prices read so far, ignoring
+/// blank lines.
+///
+/// # Arguments
+/// * `reader` - `impl BufRead` with one price per line
+/// * `period` - Number of price changes to average (commonly 14)
+/// * `policy` - Treatment of zero or negative prices
+///
+/// ### Example
+/// ```
+/// io::stream_rsi_with_policy(std::io::stdin().lock(), 14, InputPolicy::Strict);
+/// ```
+pub fn stream_rsi_with_policy(reader: impl BufRead, period: usize, policy: InputPolicy) -> impl Iterator<Item = Result<f32, IoError>> {
+    return stream_rsi_inner(reader, period, Some(policy));
+}
+
+fn stream_rsi_inner(reader: impl BufRead, period: usize, policy: Option<InputPolicy>) -> impl Iterator<Item = Result<f32, IoError>> {
+    let mut state = RsiState::new(period);
+    // Index of the next price in the series, which skips blank lines
+    let mut index = 0;
+    return reader.lines().enumerate().filter_map(move |(i, line)| {
+        let line = match line {
+            Ok(v) => v,
+            Err(error) => return Some(Err(IoError::Read(error.to_string()))),
+        };
+        let text = line.trim();
+        if text.is_empty() { return None; }
+        let price = match text.parse::<f32>() {
+            Ok(v) if v.is_finite() => v,
+            _ => return Some(Err(IoError::Parse { line: i + 1, text: text.to_string() })),
+        };
+        index += 1;
+        let price = match policy {
+            Some(policy) => match util::apply_policy(&[price], policy) {
+                Ok(v) => v[0],
+                Err(TaError::NonPositive { value, .. }) => {
+                    let error = TaError::NonPositive { index: index - 1, value: value };
+                    return Some(Err(IoError::Invalid { line: i + 1, error: error }));
+                },
+                Err(error) => return Some(Err(IoError::Invalid { line: i + 1, error: error })),
+            },
+            None => price,
+        };
+        return state.update(price).map(Ok);
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::ta::rsi;
+
+    #[test]
+    fn test_resample_hourly() {
+        let mut bars: Vec<(u64, f32, f32, f32, f32, u64)> = Vec::new();
+        for i in 0..12 {
+            let p = 10.0 + i as f32;
+            bars.push((3600 + (i * 300), p, p + 2.0, p - 1.0, p + 1.0, 100));
+        }
+        assert_eq!(resample(&bars, 3600), vec![(3600, 10.0, 23.0, 9.0, 22.0, 1200)]);
+    }
+
+    #[test]
+    fn test_resample_skips_empty_buckets() {
+        let bars = vec![
+            (0, 10.0, 12.0, 9.0, 11.0, 100),
+            (60, 11.0, 13.0, 10.0, 12.0, 100),
+            (300, 12.0, 14.0, 11.0, 13.0, 50),
+        ];
+        assert_eq!(
+            resample(&bars, 120),
+            vec![
+                (0, 10.0, 13.0, 9.0, 12.0, 200),
+                (240, 12.0, 14.0, 11.0, 13.0, 50),
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid bucket size. Received 0, but required at least 1 second.")]
+    fn test_resample_zero_bucket() {
+        resample(&[(0, 10.0, 12.0, 9.0, 11.0, 100)], 0);
+    }
+
+    #[test]
+    fn test_find_gaps_weekend() {
+        const DAY: u64 = 24 * 60 * 60;
+        // Mon-Fri followed by the next Monday
+        let timestamps = vec![0, DAY, 2 * DAY, 3 * DAY, 4 * DAY, 7 * DAY, 8 * DAY];
+        assert_eq!(find_gaps(&timestamps, DAY), vec![Gap { after_index: 4, missing: 2 }]);
+    }
+
+    #[test]
+    fn test_find_gaps_none() {
+        assert_eq!(find_gaps(&[0, 60, 120, 180], 60), vec![]);
+        assert_eq!(find_gaps(&[], 60), vec![]);
+    }
+
+    #[test]
+    fn test_find_gaps_partial_interval() {
+        assert_eq!(find_gaps(&[0, 60, 150], 60), vec![Gap { after_index: 1, missing: 1 }]);
+    }
+
+    #[test]
+    fn test_stream_rsi() {
+        let prices = vec![10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0];
+        let text: String = prices.iter().map(|p| format!("{}\n", p)).collect();
+        let streamed: Vec<Result<f32, IoError>> = stream_rsi(std::io::Cursor::new(text), 14).collect();
+        let expected: Vec<Result<f32, IoError>> = rsi::run(prices, 14).into_iter().map(Ok).collect();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_stream_rsi_malformed_line() {
+        let text = "10\n12\nabc\n\n11\n13\n";
+        let streamed: Vec<Result<f32, IoError>> = stream_rsi(std::io::Cursor::new(text), 3).collect();
+        // The bad line is reported, then skipped
+        let expected: Vec<Result<f32, IoError>> = vec![
+            Err(IoError::Parse { line: 3, text: "abc".to_string() }),
+            Ok(rsi::run(vec![10.0, 12.0, 11.0, 13.0], 3)[0]),
+        ];
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_stream_rsi_with_policy_negative_price() {
+        let text = "10\n\n12\n-11\n11\n13\n";
+        let strict: Vec<Result<f32, IoError>> = stream_rsi_with_policy(std::io::Cursor::new(text), 3, InputPolicy::Strict).collect();
+        assert_eq!(strict, vec![
+            Err(IoError::Invalid { line: 4, error: TaError::NonPositive { index: 2, value: -11.0 } }),
+            Ok(rsi::run(vec![10.0, 12.0, 11.0, 13.0], 3)[0]),
+        ]);
+        let lenient: Vec<Result<f32, IoError>> = stream_rsi_with_policy(std::io::Cursor::new(text), 3, InputPolicy::Lenient).collect();
+        let expected: Vec<Result<f32, IoError>> = rsi::run(vec![10.0, 12.0, util::MIN_PRICE, 11.0, 13.0], 3).into_iter().map(Ok).collect();
+        assert_eq!(lenient, expected);
+    }
+
+    #[test]
+    fn test_validate_series() {
+        assert_eq!(validate_series(&[0, 60, 120, 180]), Ok(()));
+        assert_eq!(validate_series::<u64>(&[]), Ok(()));
+        assert_eq!(validate_series(&[-120i64, -60, 0, 60]), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_series_out_of_order() {
+        assert_eq!(validate_series(&[0, 120, 60, 180]), Err(SeriesError::OutOfOrder { index: 2 }));
+    }
+
+    #[test]
+    fn test_validate_series_duplicate() {
+        let result = validate_series(&[0, 60, 60, 120]);
+        assert_eq!(result, Err(SeriesError::Duplicate { index: 2 }));
+        assert_eq!(result.unwrap_err().to_string(), "Duplicate timestamp. Entry 2 is equal to the entry preceding it.");
+    }
+}
@@ -0,0 +1,409 @@
+use std::collections::BTreeMap;
+
+use crate::stock::ta::{atr, cci, macd, rsi, smooth, stochastic_oscillator, williams_r, Smoothing};
+
+/// Output of a multi-indicator analysis run over a single ticker.
+///
+/// Series are keyed by name (e.g. `"rsi_14"`), and `warmup` holds the
+/// number of input bars each series skips, so `series[name][k]` corresponds
+/// to input bar `warmup[name] + k`.
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct AnalysisReport {
+    pub ticker: String,
+    pub series: BTreeMap<String, Vec<f32>>,
+    pub warmup: BTreeMap<String, usize>,
+}
+
+impl AnalysisReport {
+    pub fn new(ticker: &str) -> Self {
+        return Self {
+            ticker: ticker.to_owned(),
+            series: BTreeMap::new(),
+            warmup: BTreeMap::new(),
+        };
+    }
+
+    /// Add a named series along with its warm-up (e.g. `Indicator::warmup`).
+    pub fn add(&mut self, name: &str, values: Vec<f32>, warmup: usize) {
+        self.series.insert(name.to_owned(), values);
+        self.warmup.insert(name.to_owned(), warmup);
+    }
+}
+
+/// Indicator column of a `heatmap`.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum IndicatorSpec {
+    /// RSI of the closes with the given period.
+    Rsi(usize),
+    /// Stochastic oscillator with the given period.
+    Stochastic(usize),
+    /// Williams %R with the given period.
+    WilliamsR(usize),
+    /// CCI with the given period.
+    Cci(usize),
+}
+
+/// Largest CCI magnitude distinguished by `heatmap`; readings beyond it are clamped.
+const CCI_RANGE: f32 = 200.0;
+
+impl IndicatorSpec {
+    /// Number of input bars before the indicator's first value.
+    pub fn warmup(&self) -> usize {
+        match *self {
+            IndicatorSpec::Rsi(period) => return period,
+            IndicatorSpec::Stochastic(period) | IndicatorSpec::WilliamsR(period) | IndicatorSpec::Cci(period) => return period.saturating_sub(1),
+        }
+    }
+
+    /// Run the indicator and scale its readings to `[0, 1]`.
+    fn normalized(&self, prices: &[(f32, f32, f32)]) -> Vec<f32> {
+        match *self {
+            IndicatorSpec::Rsi(period) => {
+                let closes: Vec<f32> = prices.iter().map(|p| p.0).collect();
+                return rsi::run(closes, period).iter().map(|v| v / 100.0).collect();
+            },
+            IndicatorSpec::Stochastic(period) => return stochastic_oscillator::run(prices.to_vec(), period).iter().map(|v| v / 100.0).collect(),
+            IndicatorSpec::WilliamsR(period) => return williams_r::run(prices.to_vec(), period).iter().map(|v| (v + 100.0) / 100.0).collect(),
+            IndicatorSpec::Cci(period) => {
+                return cci::run(prices.to_vec(), period).iter().map(|v| ((v + CCI_RANGE) / (2.0 * CCI_RANGE)).clamp(0.0, 1.0)).collect();
+            },
+        }
+    }
+}
+
+/// Build a bar-by-indicator matrix of normalized readings for rendering a
+/// confluence heatmap, i.e. how many indicators agree on overbought/oversold
+/// at each bar.
+///
+/// Every reading is scaled to `[0, 1]`, where 1 is the indicator's most
+/// overbought reading:
+/// - RSI and stochastic: `v / 100`
+/// - Williams %R: `(v + 100) / 100`
+/// - CCI: clamped to `[-200, 200]`, then `(v + 200) / 400`
+///
+/// NOTE: Each row is a bar of `prices` and each column an entry of
+/// `indicators`, in order. Bars in an indicator's warm-up (or where the
+/// indicator is undefined) are `NaN`.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `indicators` - `&[IndicatorSpec]` with the indicator of each column
+///
+/// ### Example
+/// ```
+/// report::heatmap(&prices, &[IndicatorSpec::Rsi(14), IndicatorSpec::Stochastic(14)]);
+/// ```
+pub fn heatmap(prices: &[(f32, f32, f32)], indicators: &[IndicatorSpec]) -> Vec<Vec<f32>> {
+    let mut matrix: Vec<Vec<f32>> = vec![vec![f32::NAN; indicators.len()]; prices.len()];
+    for (column, spec) in indicators.iter().enumerate() {
+        if prices.len() <= spec.warmup() { continue; }
+        let values = spec.normalized(prices);
+        let offset = prices.len() - values.len();
+        for (k, &v) in values.iter().enumerate() {
+            matrix[offset + k][column] = v;
+        }
+    }
+    return matrix;
+}
+
+/// Period of the RSI, stochastic %K, ATR and volume RSI in a `Snapshot`.
+pub const SNAPSHOT_PERIOD: usize = 14;
+/// Period of the SMA of %K giving the stochastic %D in a `Snapshot`.
+pub const SNAPSHOT_D_PERIOD: usize = 3;
+
+/// Readings of the common indicators at a single bar (see `snapshot`).
+///
+/// A field is `None` while its indicator is still in its warm-up at the bar.
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Snapshot {
+    pub rsi: Option<f32>,
+    pub stoch_k: Option<f32>,
+    pub stoch_d: Option<f32>,
+    /// MACD histogram of the default 12/26/9 MACD.
+    pub macd_histogram: Option<f32>,
+    pub atr: Option<f32>,
+    pub volume_rsi: Option<f32>,
+}
+
+/// Summarize the market internals at bar `at`: the RSI, stochastic %K/%D,
+/// MACD histogram, ATR and volume RSI, each with its common settings.
+///
+/// NOTE: Only bars up to and including `at` are used, so a snapshot of a
+/// past bar shows exactly what was known at that bar.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `volumes` - `&[u64]` containing volume data for a period of time
+/// * `at` - Index of the bar to summarize
+///
+/// ### Example
+/// ```
+/// report::snapshot(&prices, &volumes, prices.len() - 1);
+/// ```
+pub fn snapshot(prices: &[(f32, f32, f32)], volumes: &[u64], at: usize) -> Snapshot {
+    if prices.len() != volumes.len() { panic!("Length mismatch. `prices` contains {} entries, but `volumes` contains {}.", prices.len(), volumes.len()); }
+    if at >= prices.len() { panic!("Index {} out of bounds. `prices` contains {} entries.", at, prices.len()); }
+    let bars = &prices[..at+1];
+    let closes: Vec<f32> = bars.iter().map(|p| p.0).collect();
+    let n = bars.len();
+
+    let ks = if n >= SNAPSHOT_PERIOD { stochastic_oscillator::run(bars.to_vec(), SNAPSHOT_PERIOD) } else { Vec::new() };
+    // The default MACD requires `slow+1` prices
+    let histogram = if n > 26 {
+        let (macds, signal) = macd::run(closes.to_vec());
+        macd::histogram(&macds, &signal)
+    } else {
+        Vec::new()
+    };
+    let warmed_up = n > SNAPSHOT_PERIOD;
+    return Snapshot {
+        rsi: if warmed_up { rsi::run(closes, SNAPSHOT_PERIOD).last().copied() } else { None },
+        stoch_k: ks.last().copied(),
+        stoch_d: smooth(&ks, Smoothing::Sma, SNAPSHOT_D_PERIOD).last().copied(),
+        macd_histogram: histogram.last().copied(),
+        atr: if warmed_up { atr::run(bars.to_vec(), SNAPSHOT_PERIOD).last().copied() } else { None },
+        volume_rsi: if warmed_up { rsi::volume_rsi(volumes[..at+1].to_vec(), SNAPSHOT_PERIOD).last().copied() } else { None },
+    };
+}
+
+/// Serialize an `AnalysisReport` to JSON for a web frontend.
+///
+/// ### Schema
+/// ```json
+/// {
+///     "ticker": "spy",
+///     "warmup": { "<name>": <usize>, ... },
+///     "series": { "<name>": [<f32>, ...], ... }
+/// }
+/// ```
+///
+/// Keys of `warmup` and `series` are emitted in sorted order so the output
+/// is stable between runs. Non-finite values (e.g. a `NaN` from a division
+/// by zero) are emitted as `null` since JSON has no representation for them.
+///
+/// ### Example
+/// ```
+/// report::to_json(&report);
+/// ```
+#[cfg(feature = "serde")]
+pub fn to_json(report: &AnalysisReport) -> String {
+    let mut output = "".to_owned();
+    output.push('{');
+    output.push_str(&format!("\"ticker\": \"{}\",", escape(&report.ticker)));
+    output.push_str("\"warmup\": {");
+    let warmups: Vec<String> = report.warmup.iter().map(|(k, v)| format!("\"{}\": {}", escape(k), v)).collect();
+    output.push_str(&warmups.join(","));
+    output.push_str("},");
+    output.push_str("\"series\": {");
+    let series: Vec<String> = report.series.iter().map(|(k, values)| {
+        let values: Vec<String> = values.iter().map(|v| if v.is_finite() { format!("{}", v) } else { "null".to_owned() }).collect();
+        return format!("\"{}\": [{}]", escape(k), values.join(","));
+    }).collect();
+    output.push_str(&series.join(","));
+    output.push_str("}}");
+    return output;
+}
+
+/// Parse the JSON produced by `to_json` back into an `AnalysisReport`.
+///
+/// NOTE: `null` values are read back as `f32::NAN`.
+///
+/// ### Example
+/// ```
+/// report::from_json(&report::to_json(&report));
+/// ```
+#[cfg(feature = "serde")]
+pub fn from_json(json: &str) -> Result<AnalysisReport, String> {
+    let root: serde_json::Value = match serde_json::from_str(json) {
+        Ok(v) => v,
+        Err(e) => return Err(format!("Could not parse the report. {}", e)),
+    };
+    let ticker = match root["ticker"].as_str() {
+        Some(v) => v,
+        None => return Err("Could not parse the report. Missing `ticker`.".to_owned()),
+    };
+    let mut report = AnalysisReport::new(ticker);
+    let warmups = match root["warmup"].as_object() {
+        Some(v) => v,
+        None => return Err("Could not parse the report. Missing `warmup`.".to_owned()),
+    };
+    for (name, warmup) in warmups {
+        match warmup.as_u64() {
+            Some(v) => report.warmup.insert(name.to_owned(), v as usize),
+            None => return Err(format!("Could not parse the report. Invalid warmup for `{}`.", name)),
+        };
+    }
+    let series = match root["series"].as_object() {
+        Some(v) => v,
+        None => return Err("Could not parse the report. Missing `series`.".to_owned()),
+    };
+    for (name, values) in series {
+        let values = match values.as_array() {
+            Some(v) => v,
+            None => return Err(format!("Could not parse the report. Invalid series `{}`.", name)),
+        };
+        let values: Vec<f32> = values.iter().map(|v| match v.as_f64() {
+            Some(v) => v as f32,
+            None => f32::NAN,
+        }).collect();
+        report.series.insert(name.to_owned(), values);
+    }
+    return Ok(report);
+}
+
+/// Escape a string for a JSON string literal: quotes, backslashes and
+/// every control character below U+0020.
+#[cfg(feature = "serde")]
+fn escape(s: &str) -> String {
+    let mut escaped = String::new();
+    for c in s.chars() {
+        match c {
+            '\\' => escaped.push_str("\\\\"),
+            '"' => escaped.push_str("\\\""),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            c if (c as u32) < 0x20 => escaped.push_str(&format!("\\u{:04x}", c as u32)),
+            c => escaped.push(c),
+        };
+    }
+    return escaped;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::testutil::geometric_brownian;
+
+    #[cfg(feature = "serde")]
+    fn report() -> AnalysisReport {
+        let mut report = AnalysisReport::new("spy");
+        report.add("rsi_14", vec![57.69231, 48.267326], 14);
+        report.add("sma_3", vec![10.0, 12.5, 13.333333], 2);
+        return report;
+    }
+
+    #[test]
+    fn test_heatmap() {
+        let prices = vec![
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+        ];
+        let specs = vec![IndicatorSpec::Rsi(5), IndicatorSpec::Stochastic(3), IndicatorSpec::WilliamsR(3), IndicatorSpec::Cci(4)];
+        let matrix = heatmap(&prices, &specs);
+        assert_eq!(matrix.len(), prices.len());
+        for (i, row) in matrix.iter().enumerate() {
+            assert_eq!(row.len(), specs.len());
+            for (column, v) in row.iter().enumerate() {
+                if i < specs[column].warmup() {
+                    assert_eq!(v.is_nan(), true);
+                } else {
+                    assert_eq!(*v >= 0.0 && *v <= 1.0, true);
+                }
+            }
+        }
+        // %R = %K - 100, so both normalize to the same reading
+        for row in matrix[2..].iter() {
+            assert_eq!((row[1] - row[2]).abs() < 1e-5, true);
+        }
+    }
+
+    #[test]
+    fn test_heatmap_not_enough_prices() {
+        let matrix = heatmap(&[(15.0, 10.0, 20.0), (18.0, 13.0, 22.0)], &[IndicatorSpec::Rsi(14)]);
+        assert_eq!(matrix.len(), 2);
+        assert_eq!(matrix.iter().all(|row| row[0].is_nan()), true);
+    }
+
+    fn bars(n: usize) -> (Vec<(f32, f32, f32)>, Vec<u64>) {
+        let closes = geometric_brownian(7, n, 0.0005, 0.02, 100.0);
+        let prices = closes.iter().map(|&c| (c, c * 0.99, c * 1.01)).collect();
+        let volumes = (0..n).map(|i| 1000 + ((i * 37) % 500) as u64).collect();
+        return (prices, volumes);
+    }
+
+    #[test]
+    fn test_snapshot() {
+        let (prices, volumes) = bars(60);
+        let at = 45;
+        let snap = snapshot(&prices, &volumes, at);
+
+        let closes: Vec<f32> = prices.iter().map(|p| p.0).collect();
+        let rsis = rsi::run(closes.to_vec(), 14);
+        assert_eq!(snap.rsi, Some(rsis[at - 14]));
+        let ks = stochastic_oscillator::run(prices.to_vec(), 14);
+        assert_eq!(snap.stoch_k, Some(ks[at - 13]));
+        let ds = smooth(&ks, Smoothing::Sma, 3);
+        assert_eq!(snap.stoch_d, Some(ds[at - 15]));
+        let (macds, signal) = macd::run(closes.to_vec());
+        let histogram = macd::histogram(&macds, &signal);
+        assert_eq!(snap.macd_histogram, Some(histogram[at - 35]));
+        let atrs = atr::run(prices.to_vec(), 14);
+        assert_eq!(snap.atr, Some(atrs[at - 14]));
+        let volume_rsis = rsi::volume_rsi(volumes.to_vec(), 14);
+        assert_eq!(snap.volume_rsi, Some(volume_rsis[at - 14]));
+    }
+
+    #[test]
+    fn test_snapshot_warmup() {
+        let (prices, volumes) = bars(30);
+        let snap = snapshot(&prices, &volumes, 14);
+        assert_eq!(snap.rsi.is_some(), true);
+        // %D requires 3 values of %K
+        assert_eq!(snap.stoch_k.is_some(), true);
+        assert_eq!(snap.stoch_d, None);
+        assert_eq!(snapshot(&prices, &volumes, 15).stoch_d.is_some(), true);
+        assert_eq!(snap.macd_histogram, None);
+        assert_eq!(snapshot(&prices, &volumes, 12), Snapshot {
+            rsi: None, stoch_k: None, stoch_d: None, macd_histogram: None, atr: None, volume_rsi: None,
+        });
+    }
+
+    #[test]
+    #[should_panic(expected = "Index 30 out of bounds. `prices` contains 30 entries.")]
+    fn test_snapshot_out_of_bounds() {
+        let (prices, volumes) = bars(30);
+        snapshot(&prices, &volumes, 30);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_to_json() {
+        let json = to_json(&report());
+        assert_eq!(json, "{\"ticker\": \"spy\",\"warmup\": {\"rsi_14\": 14,\"sma_3\": 2},\"series\": {\"rsi_14\": [57.69231,48.267326],\"sma_3\": [10,12.5,13.333333]}}");
+        let root: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(root["ticker"], "spy");
+        assert_eq!(root["warmup"]["rsi_14"], 14);
+        assert_eq!(root["series"]["sma_3"].as_array().unwrap().len(), 3);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_round_trip() {
+        let report = report();
+        assert_eq!(from_json(&to_json(&report)), Ok(report));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_round_trip_control_characters() {
+        let mut report = AnalysisReport::new("spy\n\t\"q\"\\\r\u{1}");
+        report.add("rsi\u{1f}", vec![50.0], 14);
+        assert_eq!(to_json(&report).contains("spy\\n\\t\\\"q\\\"\\\\\\r\\u0001"), true);
+        assert_eq!(from_json(&to_json(&report)), Ok(report));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_from_json_invalid() {
+        assert_eq!(from_json("{\"ticker\": \"spy\"}"), Err("Could not parse the report. Missing `warmup`.".to_owned()));
+    }
+}
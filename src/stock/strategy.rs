@@ -0,0 +1,552 @@
+use std::ops::Range;
+
+use crate::stock::ta::{crossovers, macd, rsi, sma, stochastic_oscillator, CrossDirection};
+
+/// A completed long position.
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Trade {
+    /// Index of the bar the position was bought at (on its close).
+    pub entry_index: usize,
+    /// Index of the bar the position was sold at (on its close).
+    pub exit_index: usize,
+    /// Percent return of the trade (e.g. `10.0` for a 10% gain).
+    pub return_pct: f32,
+}
+
+impl Trade {
+    pub fn new(prices: &[f32], entry_index: usize, exit_index: usize) -> Self {
+        return Self {
+            entry_index: entry_index,
+            exit_index: exit_index,
+            return_pct: ((prices[exit_index] / prices[entry_index]) - 1.0) * 100.0,
+        };
+    }
+}
+
+/// Per-bar action suggested by an indicator.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Signal {
+    Buy,
+    Sell,
+    Neutral,
+}
+
+/// Majority vote of several indicators' `Signal`s on a single bar.
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Consensus {
+    pub signal: Signal,
+    /// Number of indicators that voted for `signal`.
+    pub votes: usize,
+}
+
+/// Backtest a long/flat moving average crossover strategy on a `&[f32]` of
+/// price data.
+///
+/// ### Rules
+/// - Buy on the close of the bar where the fast SMA crosses above the slow SMA (golden cross)
+/// - Sell on the close of the bar where the fast SMA crosses below the slow SMA (death cross)
+///
+/// NOTE: A position still open at the end of the series is closed on the
+/// last bar so its unrealized return is included.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `fast` - Number of periods for the fast SMA
+/// * `slow` - Number of periods for the slow SMA
+///
+/// ### Example
+/// ```
+/// strategy::sma_crossover(&prices, 50, 200);
+/// ```
+pub fn sma_crossover(prices: &[f32], fast: usize, slow: usize) -> Vec<Trade> {
+    if fast == 0 || fast >= slow { panic!("Invalid periods for SMA crossover. Received fast {} and slow {}, but required 0 < fast < slow.", fast, slow); }
+    if prices.len() < slow { panic!("Not enough entries to calculate the SMA crossover. Received {}, but required {}.", prices.len(), slow); }
+    let fast_smas = sma::run(prices.to_vec(), fast);
+    let slow_smas = sma::run(prices.to_vec(), slow);
+
+    // `slow_smas[0]` corresponds to `prices[slow-1]`, so align the fast SMA to it
+    let offset = slow - 1;
+    let mut trades: Vec<Trade> = Vec::new();
+    let mut entry: Option<usize> = None;
+    for cross in crossovers(&fast_smas[slow-fast..], &slow_smas) {
+        let index = cross.index + offset;
+        match (cross.direction, entry) {
+            (CrossDirection::Up, None) => entry = Some(index),
+            (CrossDirection::Down, Some(e)) => {
+                trades.push(Trade::new(prices, e, index));
+                entry = None;
+            },
+            _ => {},
+        };
+    }
+    if let Some(e) = entry {
+        if e < prices.len()-1 { trades.push(Trade::new(prices, e, prices.len()-1)); }
+    }
+    return trades;
+}
+
+/// Backtest a long/flat RSI mean-reversion strategy on a `&[f32]` of price data.
+///
+/// ### Rules
+/// - Buy on the close of the bar where the RSI drops below `buy_below` (oversold)
+/// - Sell on the close of the bar where the RSI rises above `sell_above` (overbought)
+///
+/// NOTE: A position still open at the end of the series is closed on the
+/// last bar so its unrealized return is included. A buy signal on the last
+/// bar itself is ignored since it could not be held.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `period` - Number of periods for the RSI
+/// * `buy_below` - RSI level to buy under (commonly 30)
+/// * `sell_above` - RSI level to sell over (commonly 70)
+///
+/// ### Example
+/// ```
+/// strategy::rsi_reversion(&prices, 14, 30.0, 70.0);
+/// ```
+pub fn rsi_reversion(prices: &[f32], period: usize, buy_below: f32, sell_above: f32) -> Vec<Trade> {
+    let rsis = rsi::run(prices.to_vec(), period);
+
+    // `rsis[0]` corresponds to `prices[period]`
+    let mut trades: Vec<Trade> = Vec::new();
+    let mut entry: Option<usize> = None;
+    for i in 0..rsis.len() {
+        let index = i + period;
+        match entry {
+            None => if rsis[i] < buy_below { entry = Some(index); },
+            Some(e) => if rsis[i] > sell_above {
+                trades.push(Trade::new(prices, e, index));
+                entry = None;
+            },
+        };
+    }
+    if let Some(e) = entry {
+        if e < prices.len()-1 { trades.push(Trade::new(prices, e, prices.len()-1)); }
+    }
+    return trades;
+}
+
+/// Compound the returns of a series of `Trade`s into an equity curve.
+///
+/// Each trade is assumed to invest the full account, so the equity after a
+/// trade is `equity_prev * (1 + return_pct / 100)`.
+///
+/// # Returns
+/// `Vec<f32>` starting with `starting_capital` followed by the equity after
+/// each trade (`trades.len() + 1` entries), suitable for plotting or passing
+/// to `ta::max_drawdown`.
+///
+/// # Arguments
+/// * `trades` - `&[Trade]` in the order they were made
+/// * `starting_capital` - Equity before the first trade
+///
+/// ### Example
+/// ```
+/// strategy::equity_curve(&strategy::sma_crossover(&prices, 50, 200), 10000.0);
+/// ```
+pub fn equity_curve(trades: &[Trade], starting_capital: f32) -> Vec<f32> {
+    let mut equity: Vec<f32> = vec![starting_capital];
+    let mut current = starting_capital;
+    for trade in trades {
+        current *= 1.0 + (trade.return_pct / 100.0);
+        equity.push(current);
+    }
+    return equity;
+}
+
+/// Calculate the number of shares to buy so that a stop `atr_mult` ATRs
+/// from the entry risks `risk_pct` percent of the account.
+///
+/// ### Formula
+/// `shares = floor((account * (risk_pct / 100)) / (atr_mult * atr))`
+///
+/// Sizing by volatility risks the same amount on every trade: volatile
+/// securities get a wider stop and fewer shares, quiet ones a tighter stop
+/// and more shares.
+///
+/// NOTE: The share count is rounded down, so the risk never exceeds
+/// `risk_pct` of the account.
+///
+/// # Arguments
+/// * `account` - Equity of the account
+/// * `risk_pct` - Percent of the account to risk on the trade (e.g. `1.0` for 1%)
+/// * `atr` - Current ATR of the security (e.g. the last value of `atr::run`)
+/// * `atr_mult` - Distance of the stop from the entry in ATRs (commonly 2)
+///
+/// ### Example
+/// ```
+/// strategy::atr_position_size(100000.0, 1.0, atrs[atrs.len()-1], 2.0);
+/// ```
+pub fn atr_position_size(account: f32, risk_pct: f32, atr: f32, atr_mult: f32) -> f32 {
+    let stop_distance = atr_mult * atr;
+    if stop_distance.is_nan() || stop_distance <= 0.0 { panic!("Invalid stop distance. Received {} ({} * {} ATR), but required a positive distance.", stop_distance, atr_mult, atr); }
+    return ((account * (risk_pct / 100.0)) / stop_distance).floor();
+}
+
+/// Combine the `Signal`s of several indicators into a single majority-vote
+/// `Consensus` per bar.
+///
+/// ### Alignment
+/// Indicators have different warm-ups, so their signal series are of
+/// different lengths. Every series is assumed to end on the same (latest)
+/// bar, so they are aligned from the end. The output is as long as the
+/// longest series, and a series that has not started yet on a bar (still in
+/// its warm-up) abstains from that bar's vote.
+///
+/// ### Ties
+/// The signal with strictly the most votes wins. Any tie for the most votes
+/// (e.g. one buy, one sell and one neutral) results in `Signal::Neutral`,
+/// with `votes` set to the number of explicit neutral votes.
+///
+/// # Arguments
+/// * `signals` - `&[Vec<Signal>]` containing one signal series per indicator
+///
+/// ### Example
+/// ```
+/// strategy::consensus(&vec![rsi_signals, stoch_signals, macd_signals]);
+/// ```
+pub fn consensus(signals: &[Vec<Signal>]) -> Vec<Consensus> {
+    let len = match signals.iter().map(|s| s.len()).max() {
+        Some(v) => v,
+        None => return Vec::new(),
+    };
+    let mut consensus: Vec<Consensus> = Vec::new();
+    for i in 0..len {
+        let mut buys = 0;
+        let mut sells = 0;
+        let mut neutrals = 0;
+        for series in signals {
+            // Bars before the series starts are part of its warm-up
+            let offset = len - series.len();
+            if i < offset { continue; }
+            match series[i-offset] {
+                Signal::Buy => buys += 1,
+                Signal::Sell => sells += 1,
+                Signal::Neutral => neutrals += 1,
+            };
+        }
+        if buys > sells && buys > neutrals {
+            consensus.push(Consensus { signal: Signal::Buy, votes: buys });
+        } else if sells > buys && sells > neutrals {
+            consensus.push(Consensus { signal: Signal::Sell, votes: sells });
+        } else {
+            consensus.push(Consensus { signal: Signal::Neutral, votes: neutrals });
+        }
+    }
+    return consensus;
+}
+
+/// Filter a `&[Signal]` series so a signal is only acted on once it has
+/// held for `bars` consecutive bars, suppressing one-off whipsaws (e.g. a
+/// crossover that reverses on the next bar).
+///
+/// Each bar passes through its signal if it is the `bars`-th (or later)
+/// consecutive bar with that signal, and is `Signal::Neutral` otherwise. A
+/// `bars` of 0 or 1 leaves the series unchanged.
+///
+/// NOTE: Confirmation always lags the raw signal by `bars - 1` bars.
+///
+/// # Arguments
+/// * `signals` - `&[Signal]` containing one signal per bar
+/// * `bars` - Number of consecutive bars a signal must hold
+///
+/// ### Example
+/// ```
+/// strategy::confirm(&strategy::triple_screen(&weekly, &daily), 3);
+/// ```
+pub fn confirm(signals: &[Signal], bars: usize) -> Vec<Signal> {
+    let mut confirmed: Vec<Signal> = Vec::new();
+    // Number of consecutive bars the current signal has held
+    let mut held = 0;
+    for i in 0..signals.len() {
+        if i > 0 && signals[i] == signals[i-1] {
+            held += 1;
+        } else {
+            held = 1;
+        }
+        if held >= bars {
+            confirmed.push(signals[i]);
+        } else {
+            confirmed.push(Signal::Neutral);
+        }
+    }
+    return confirmed;
+}
+
+/// Number of daily bars in each weekly bar for `triple_screen`.
+pub const DAYS_PER_WEEK: usize = 5;
+/// Period of the daily stochastic oscillator in `triple_screen`.
+pub const TRIPLE_SCREEN_STOCH_PERIOD: usize = 5;
+
+/// Generate entries with Elder's Triple Screen on weekly closes and daily
+/// `(close, low, high)` bars.
+///
+/// ### Rules
+/// 1. The slope of the weekly MACD (12, 26, 9) sets the tide: only longs
+///    while it is rising, only shorts while it is falling.
+/// 2. The daily 5-period stochastic oscillator times the entry against the
+///    tide: `Buy` when it is oversold (below 30) in a weekly uptrend, and
+///    `Sell` when it is overbought (above 70) in a weekly downtrend.
+///
+/// Every other bar (including the warm-up of either indicator) is `Neutral`.
+///
+/// NOTE: Both series must end on the same day, and each weekly bar is
+/// assumed to span `DAYS_PER_WEEK` (5) daily bars counting back from the
+/// end. A weekly value is only final once the week closes, so for a
+/// backtest without lookahead pass weekly closes lagged by one week.
+///
+/// # Arguments
+/// * `weekly` - `&[f32]` containing weekly closing prices
+/// * `daily` - `&[(f32, f32, f32)]` containing daily prices in the format of `&[(close, low, high)]`
+///
+/// # Returns
+/// `Vec<Signal>` with one signal per daily bar.
+///
+/// ### Example
+/// ```
+/// strategy::triple_screen(&weekly_closes, &daily_bars);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/t/triple-screen-trading-system.asp
+pub fn triple_screen(weekly: &[f32], daily: &[(f32, f32, f32)]) -> Vec<Signal> {
+    let mut signals: Vec<Signal> = vec![Signal::Neutral; daily.len()];
+    // The MACD needs 27 weeks, and a slope one more
+    if weekly.len() < 28 || daily.len() < TRIPLE_SCREEN_STOCH_PERIOD { return signals; }
+    let (macds, _) = macd::run(weekly.to_vec());
+    let stochs = stochastic_oscillator::run(daily.to_vec(), TRIPLE_SCREEN_STOCH_PERIOD);
+    let stoch_offset = daily.len() - stochs.len();
+    for i in stoch_offset..daily.len() {
+        let weeks_back = (daily.len() - 1 - i) / DAYS_PER_WEEK;
+        // The week's MACD and the one before it are needed for the slope
+        let w = match (macds.len() - 1).checked_sub(weeks_back) {
+            Some(w) if w >= 1 => w,
+            _ => continue,
+        };
+        let slope = macds[w] - macds[w-1];
+        let stoch = stochs[i - stoch_offset];
+        if slope > 0.0 && stoch < 30.0 {
+            signals[i] = Signal::Buy;
+        } else if slope < 0.0 && stoch > 70.0 {
+            signals[i] = Signal::Sell;
+        }
+    }
+    return signals;
+}
+
+/// Evaluate a strategy for every period in `periods`, e.g. to find the RSI
+/// period with the best backtest.
+///
+/// `eval` runs the strategy on `prices` with the given period and returns
+/// its score (e.g. the final equity or total return). Periods are evaluated
+/// in order on the current thread.
+///
+/// NOTE: The best-scoring period is likely overfit to `prices`. Confirm it
+/// on data that was not part of the sweep.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `periods` - `Range<usize>` of periods to evaluate
+/// * `eval` - Scores the strategy for `(prices, period)`
+///
+/// # Returns
+/// `Vec<(usize, f32)>` with one `(period, score)` per period, in order.
+///
+/// ### Example
+/// ```
+/// strategy::sweep(&prices, 5..30, |prices, period| {
+///     let trades = strategy::rsi_reversion(prices, period, 30.0, 70.0);
+///     return strategy::equity_curve(&trades, 100.0).pop().unwrap_or(100.0);
+/// });
+/// ```
+pub fn sweep<F>(prices: &[f32], periods: Range<usize>, mut eval: F) -> Vec<(usize, f32)>
+where
+    F: FnMut(&[f32], usize) -> f32,
+{
+    let mut scores: Vec<(usize, f32)> = Vec::new();
+    for period in periods {
+        scores.push((period, eval(prices, period)));
+    }
+    return scores;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sma_crossover() {
+        let prices = vec![
+            10.0, 9.0, 8.0, 7.0, 6.0,
+            8.0, 10.0, 12.0, 14.0, 16.0,
+            14.0, 12.0, 10.0, 8.0, 6.0,
+        ];
+        let trades = sma_crossover(&prices, 2, 4);
+        assert_eq!(trades, vec![Trade { entry_index: 6, exit_index: 11, return_pct: 20.000004 }]);
+    }
+
+    #[test]
+    fn test_sma_crossover_open_position() {
+        let prices = vec![10.0, 9.0, 8.0, 7.0, 6.0, 8.0, 10.0, 12.0, 14.0];
+        let trades = sma_crossover(&prices, 2, 4);
+        assert_eq!(trades, vec![Trade { entry_index: 6, exit_index: 8, return_pct: 39.999996 }]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid periods for SMA crossover. Received fast 4 and slow 2, but required 0 < fast < slow.")]
+    fn test_sma_crossover_invalid_periods() {
+        sma_crossover(&[10.0, 9.0, 8.0, 7.0], 4, 2);
+    }
+
+    #[test]
+    fn test_rsi_reversion() {
+        // RSI: [59.2, 48.3, 49.5, 51.1, 51.5, 49.6, 49.3, 61.0, 57.5, 47.2]
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let trades = rsi_reversion(&prices, 14, 50.0, 55.0);
+        assert_eq!(trades, vec![Trade { entry_index: 15, exit_index: 21, return_pct: 4100.0 }]);
+    }
+
+    #[test]
+    fn test_rsi_reversion_no_signal() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        assert_eq!(rsi_reversion(&prices, 14, 30.0, 70.0), vec![]);
+    }
+
+    #[test]
+    fn test_equity_curve() {
+        let trades = vec![
+            Trade { entry_index: 0, exit_index: 1, return_pct: 10.0 },
+            Trade { entry_index: 2, exit_index: 3, return_pct: 10.0 },
+        ];
+        assert_eq!(equity_curve(&trades, 100.0), vec![100.0, 110.0, 121.0]);
+    }
+
+    #[test]
+    fn test_equity_curve_no_trades() {
+        assert_eq!(equity_curve(&[], 100.0), vec![100.0]);
+    }
+
+    #[test]
+    fn test_consensus_two_of_three_buy() {
+        let signals = vec![
+            vec![Signal::Buy, Signal::Neutral],
+            vec![Signal::Buy, Signal::Sell],
+            vec![Signal::Sell, Signal::Sell],
+        ];
+        assert_eq!(consensus(&signals), vec![
+            Consensus { signal: Signal::Buy, votes: 2 },
+            Consensus { signal: Signal::Sell, votes: 2 },
+        ]);
+    }
+
+    #[test]
+    fn test_consensus_tie_and_warmup() {
+        let signals = vec![
+            vec![Signal::Buy, Signal::Buy, Signal::Buy],
+            vec![Signal::Sell],
+            vec![Signal::Neutral, Signal::Neutral],
+        ];
+        assert_eq!(consensus(&signals), vec![
+            // Only the first indicator has warmed up
+            Consensus { signal: Signal::Buy, votes: 1 },
+            // Buy and neutral tie
+            Consensus { signal: Signal::Neutral, votes: 1 },
+            // Buy, sell and neutral tie
+            Consensus { signal: Signal::Neutral, votes: 1 },
+        ]);
+    }
+
+    #[test]
+    fn test_consensus_empty() {
+        assert_eq!(consensus(&[]), vec![]);
+    }
+
+    #[test]
+    fn test_atr_position_size() {
+        // Risk $1,000 with a $5 stop (2 * $2.50 ATR)
+        assert_eq!(atr_position_size(100000.0, 1.0, 2.5, 2.0), 200.0);
+        // Partial shares are rounded down
+        assert_eq!(atr_position_size(100000.0, 1.0, 3.0, 2.0), 166.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid stop distance. Received 0 (2 * 0 ATR), but required a positive distance.")]
+    fn test_atr_position_size_zero_atr() {
+        atr_position_size(100000.0, 1.0, 0.0, 2.0);
+    }
+
+    #[test]
+    fn test_confirm() {
+        use Signal::{Buy, Neutral, Sell};
+        // A one-bar sell blip, then a persistent buy
+        let signals = vec![Neutral, Sell, Neutral, Buy, Buy, Buy, Buy, Neutral];
+        assert_eq!(confirm(&signals, 3), vec![Neutral, Neutral, Neutral, Neutral, Neutral, Buy, Buy, Neutral]);
+        assert_eq!(confirm(&signals, 1), signals);
+        assert_eq!(confirm(&signals, 0), signals);
+        assert_eq!(confirm(&signals, 5), vec![Neutral; 8]);
+    }
+
+    #[test]
+    fn test_triple_screen_uptrend_pullback() {
+        // Accelerating weekly trend, so the MACD keeps rising
+        let weekly: Vec<f32> = (0..40).map(|i| 100.0 + 0.1 * (i * i) as f32).collect();
+        // Daily bars rising, then a sharp dip on the last two days
+        let mut daily: Vec<(f32, f32, f32)> = (0..20).map(|i| {
+            let c = 150.0 + i as f32;
+            (c, c - 1.0, c + 1.0)
+        }).collect();
+        daily.push((165.0, 164.0, 170.0));
+        daily.push((163.0, 162.0, 166.0));
+        let signals = triple_screen(&weekly, &daily);
+        assert_eq!(signals.len(), daily.len());
+        assert_eq!(signals[signals.len()-1], Signal::Buy);
+        // Overbought days in the uptrend are not shorted
+        assert_eq!(signals[..20].iter().all(|&s| s == Signal::Neutral), true);
+    }
+
+    #[test]
+    fn test_triple_screen_downtrend_ignores_oversold() {
+        let weekly: Vec<f32> = (0..40).map(|i| 300.0 - 0.1 * (i * i) as f32).collect();
+        let mut daily: Vec<(f32, f32, f32)> = (0..20).map(|i| {
+            let c = 150.0 - i as f32;
+            (c, c - 1.0, c + 1.0)
+        }).collect();
+        daily.push((135.0, 130.0, 136.0));
+        daily.push((137.0, 134.0, 138.0));
+        let signals = triple_screen(&weekly, &daily);
+        assert_eq!(signals[signals.len()-1], Signal::Sell);
+        assert_eq!(signals.contains(&Signal::Buy), false);
+    }
+
+    #[test]
+    fn test_triple_screen_not_enough_weeks() {
+        let daily = vec![(10.0, 9.0, 11.0); 10];
+        assert_eq!(triple_screen(&[10.0; 5], &daily), vec![Signal::Neutral; 10]);
+    }
+
+    #[test]
+    fn test_sweep_rsi_period() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let scores = sweep(&prices, 5..15, |prices, period| {
+            let trades = rsi_reversion(prices, period, 50.0, 55.0);
+            return match equity_curve(&trades, 100.0).pop() {
+                Some(v) => v,
+                None => 100.0,
+            };
+        });
+        assert_eq!(scores.len(), 10);
+        let periods: Vec<usize> = scores.iter().map(|s| s.0).collect();
+        assert_eq!(periods, (5..15).collect::<Vec<usize>>());
+        // Matches the single backtest of `test_rsi_reversion`
+        assert_eq!(scores[9], (14, 4200.0));
+    }
+
+    #[test]
+    fn test_sweep_empty_range() {
+        assert_eq!(sweep(&[10.0], 5..5, |_, _| 1.0), vec![]);
+    }
+}
@@ -0,0 +1,102 @@
+/// How an indicator's output lines up with its input bars.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Alignment {
+    /// Warm-up bars are left out, so the output is shorter than the input
+    /// (the default of every `run`).
+    Trimmed,
+    /// Warm-up bars are filled with `None`, so `output[i]` corresponds to
+    /// `input[i]`, which is what most charting libraries expect.
+    PaddedFront,
+}
+
+/// How helpers combining two aligned series treat a bar where either input
+/// has no value (`None` or `NaN`), e.g. during one series' warm-up.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum GapPolicy {
+    /// A gap in either input is a gap in the output, and nothing carries
+    /// over it.
+    PropagateNone,
+    /// Bars with a gap in either input are skipped as if they were not in
+    /// the series.
+    SkipBar,
+}
+
+/// Value of an aligned entry, treating `NaN` the same as `None`.
+pub fn present(value: Option<f32>) -> Option<f32> {
+    return value.filter(|v| !v.is_nan());
+}
+
+/// Indicator output along with the input index of its first value, so it
+/// can be lined up with the input's timestamps without re-deriving the
+/// warm-up.
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Series {
+    pub values: Vec<f32>,
+    /// Index in the input that `values[0]` corresponds to.
+    pub start_index: usize,
+}
+
+impl Series {
+    /// Value corresponding to input bar `index`, if there is one.
+    pub fn value_at(&self, index: usize) -> Option<f32> {
+        if index < self.start_index { return None; }
+        return self.values.get(index - self.start_index).copied();
+    }
+}
+
+/// Align a trimmed indicator output to its input.
+///
+/// NOTE: Assumes the warm-up is at the front, i.e. the last value of
+/// `values` corresponds to the last input bar.
+///
+/// # Arguments
+/// * `values` - `Vec<f32>` containing the trimmed output of an indicator's `run`
+/// * `input_len` - Number of input bars the indicator was calculated on
+/// * `alignment` - Alignment of the returned values
+///
+/// ### Example
+/// ```
+/// ta::align(rsi::run(prices.to_vec(), 14), prices.len(), Alignment::PaddedFront);
+/// ```
+pub fn align(values: Vec<f32>, input_len: usize, alignment: Alignment) -> Vec<Option<f32>> {
+    if values.len() > input_len { panic!("Length mismatch. `values` contains {} entries, but the input only contains {}.", values.len(), input_len); }
+    let mut aligned: Vec<Option<f32>> = Vec::new();
+    if alignment == Alignment::PaddedFront {
+        aligned.resize(input_len - values.len(), None);
+    }
+    aligned.extend(values.into_iter().map(Some));
+    return aligned;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_series_value_at() {
+        let series = Series { values: vec![1.0, 2.0], start_index: 2 };
+        assert_eq!(series.value_at(1), None);
+        assert_eq!(series.value_at(2), Some(1.0));
+        assert_eq!(series.value_at(3), Some(2.0));
+        assert_eq!(series.value_at(4), None);
+    }
+
+    #[test]
+    fn test_align_trimmed() {
+        assert_eq!(align(vec![1.0, 2.0], 4, Alignment::Trimmed), vec![Some(1.0), Some(2.0)]);
+    }
+
+    #[test]
+    fn test_align_padded_front() {
+        assert_eq!(align(vec![1.0, 2.0], 4, Alignment::PaddedFront), vec![None, None, Some(1.0), Some(2.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `values` contains 2 entries, but the input only contains 1.")]
+    fn test_align_length_mismatch() {
+        align(vec![1.0, 2.0], 1, Alignment::PaddedFront);
+    }
+}
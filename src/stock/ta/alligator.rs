@@ -0,0 +1,100 @@
+use crate::stock::ta::smma;
+
+const JAW: (usize, usize) = (13, 8);
+const TEETH: (usize, usize) = (8, 5);
+const LIPS: (usize, usize) = (5, 3);
+
+/// Calculate the Williams Alligator of a `Vec<f32>` of median price data.
+///
+/// ### Definition
+/// Three smoothed moving averages of the median price, each shifted forward
+/// in time. The jaw (blue) is the slowest line, the teeth (red) the middle
+/// line, and the lips (green) the fastest line.
+///
+/// ### Formula
+/// `jaw[t] = smma(median, 13)[t - 8]`
+///
+/// `teeth[t] = smma(median, 8)[t - 5]`
+///
+/// `lips[t] = smma(median, 5)[t - 3]`
+///
+/// - `median`: `(high + low) / 2` of each bar
+/// - `t`: bar the line is plotted at
+///
+/// ### Alignment
+/// All three lines start at the jaw's first bar, so index `k` of every line
+/// is plotted at `prices[20 + k]` (the 13-period warm-up plus the 8 bar shift).
+/// Because of the forward shift each line also projects into the future:
+/// the jaw has 8 values past the last bar, the teeth 5 and the lips 3, so
+/// the lines are of different lengths.
+///
+/// ### Usage
+/// While the lines are intertwined the Alligator is "sleeping" and the
+/// market is ranging. When they fan out with the lips above the teeth above
+/// the jaw the Alligator is "eating" an uptrend (reversed for a downtrend).
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing median prices for a period of time
+///   (see `ta::medians`)
+///
+/// # Returns
+/// `(Vec<f32>, Vec<f32>, Vec<f32>)` in the format of `(jaw, teeth, lips)`.
+///
+/// ### Example
+/// ```
+/// alligator::run(ta::medians(stock.get_historical_data()));
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/articles/trading/072115/exploring-williams-alligator-indicator.asp
+pub fn run(prices: Vec<f32>) -> (Vec<f32>, Vec<f32>, Vec<f32>) {
+    if prices.len() < JAW.0 { panic!("Not enough entries to calculate the Alligator. Received {}, but required {}.", prices.len(), JAW.0); }
+    // First bar the jaw is plotted at
+    let start = JAW.0 - 1 + JAW.1;
+    let jaw = shifted(&prices, JAW, start);
+    let teeth = shifted(&prices, TEETH, start);
+    let lips = shifted(&prices, LIPS, start);
+    return (jaw, teeth, lips);
+}
+
+/// SMMA of `prices` shifted forward by `shift` bars, trimmed to start at bar `start`.
+fn shifted(prices: &[f32], (period, shift): (usize, usize), start: usize) -> Vec<f32> {
+    let smmas = smma::run(prices.to_vec(), period);
+    // `smmas[0]` is plotted at bar `period - 1 + shift`
+    let skip = start - (period - 1 + shift);
+    return smmas[skip.min(smmas.len())..].to_vec();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_alignment() {
+        let prices: Vec<f32> = (0..30).map(|i| 10.0 + i as f32).collect();
+        let (jaw, teeth, lips) = run(prices.to_vec());
+        assert_eq!(jaw.len(), 18);
+        assert_eq!(teeth.len(), 15);
+        assert_eq!(lips.len(), 13);
+        // The jaw at bar 20 is the 13-period SMMA as of bar 12
+        assert_eq!(jaw[0], smma::run(prices[0..13].to_vec(), 13)[0]);
+        // The lips at bar 20 are the 5-period SMMA as of bar 17
+        assert_eq!(lips[0], *smma::run(prices[0..18].to_vec(), 5).last().unwrap());
+    }
+
+    #[test]
+    fn test_run_uptrend_ordered() {
+        let prices: Vec<f32> = (0..40).map(|i| 10.0 + 2.0 * i as f32).collect();
+        let (jaw, teeth, lips) = run(prices);
+        for i in 0..lips.len() {
+            assert_eq!(lips[i] > teeth[i], true);
+            assert_eq!(teeth[i] > jaw[i], true);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the Alligator. Received 5, but required 13.")]
+    fn test_run_not_enough_elements() {
+        run(vec![10.0, 11.0, 12.0, 13.0, 14.0]);
+    }
+}
@@ -0,0 +1,112 @@
+use crate::stock::ta::{smooth, Smoothing};
+
+/// Calculate the average true range (ATR) based on a `Vec<(f32, f32, f32)>`
+/// of price data.
+///
+/// ### Definition
+/// A measure of volatility: the average of each bar's true range, which
+/// extends the bar's high-low range to include any gap from the previous
+/// close.
+///
+/// ### Formula
+/// `tr = max(h - l, |h - c_prev|, |l - c_prev|)`
+///
+/// `atr = smma(tr, n)`
+///
+/// - `c_prev`: previous bar's close price
+/// - `n`: number of periods to average (commonly 14)
+///
+/// NOTE: The true range requires the previous close, so the first ATR value
+/// corresponds to `prices[n]`.
+///
+/// ### Usage
+/// The ATR does not indicate direction. It is mostly used to size positions
+/// and place stops (e.g. 2 ATRs below the entry) relative to how much the
+/// security typically moves.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of periods to average
+///
+/// ### Example
+/// ```
+/// atr::run(prices, 14);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/a/atr.asp
+pub fn run(prices: Vec<(f32, f32, f32)>, period: usize) -> Vec<f32> {
+    return run_with_smoothing(prices, period, Smoothing::Wilder);
+}
+
+/// Calculate the ATR, averaging the true ranges with `smoothing` instead of
+/// Wilder's smoothing, e.g. for comparability with tools using an EMA.
+///
+/// NOTE: With `Smoothing::Ema` the SMA seed is not emitted, so the first
+/// value corresponds to `prices[period+1]` rather than `prices[period]`.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of periods to average
+/// * `smoothing` - Moving average of the true ranges (`run` uses `Smoothing::Wilder`)
+///
+/// ### Example
+/// ```
+/// atr::run_with_smoothing(prices, 14, Smoothing::Ema);
+/// ```
+pub fn run_with_smoothing(prices: Vec<(f32, f32, f32)>, period: usize, smoothing: Smoothing) -> Vec<f32> {
+    if period == 0 || prices.len() < period+1 { panic!("Not enough entries to calculate the ATR. Received {}, but required {} (period+1).", prices.len(), period+1); }
+    let mut trs: Vec<f32> = Vec::new();
+    for i in 1..prices.len() {
+        let prev_close = prices[i-1].0;
+        let (_, low, high) = prices[i];
+        trs.push((high - low).max((high - prev_close).abs()).max((low - prev_close).abs()));
+    }
+    return smooth(&trs, smoothing, period);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        // True ranges: [3, 3, 5]
+        let prices = vec![(10.0, 9.0, 11.0), (12.0, 10.0, 13.0), (11.0, 9.0, 12.0), (15.0, 13.0, 16.0)];
+        assert_eq!(run(prices, 3), vec![3.6666667]);
+    }
+
+    #[test]
+    fn test_run_complex() {
+        let prices = vec![(10.0, 9.0, 11.0), (12.0, 10.0, 13.0), (11.0, 9.0, 12.0), (15.0, 13.0, 16.0), (15.0, 14.0, 16.0)];
+        assert_eq!(run(prices, 3), vec![3.6666667, 3.1111114]);
+    }
+
+    #[test]
+    fn test_run_with_smoothing_gaps() {
+        // Gap up on bar 3 and gap down on bar 5
+        let prices = vec![
+            (10.0, 9.0, 11.0), (10.5, 9.5, 11.0), (10.0, 9.5, 10.5),
+            (15.0, 14.5, 15.5), (15.0, 14.5, 15.5), (10.0, 9.5, 10.5),
+            (10.0, 9.5, 10.5), (10.0, 9.5, 10.5),
+        ];
+        let wilder = run_with_smoothing(prices.to_vec(), 3, Smoothing::Wilder);
+        let ema = run_with_smoothing(prices.to_vec(), 3, Smoothing::Ema);
+        assert_eq!(wilder, run(prices, 3));
+        assert_eq!(ema.len(), wilder.len() - 1);
+        // The EMA reacts faster to the gaps, so the two disagree
+        for i in 0..ema.len() {
+            assert_ne!(ema[i], wilder[i+1]);
+        }
+        // ... and decays faster after the gap down
+        assert_eq!(ema[ema.len()-1] < wilder[wilder.len()-1], true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the ATR. Received 2, but required 4 (period+1).")]
+    fn test_run_not_enough_elements() {
+        run(vec![(10.0, 9.0, 11.0), (12.0, 10.0, 13.0)], 3);
+    }
+}
@@ -1,4 +1,26 @@
-use crate::stock::ta::sma;
+use crate::stock::ta::{sma, stats::std_dev};
+
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum BandSide {
+    /// Closes above the upper band.
+    Upper,
+    /// Closes below the lower band.
+    Lower,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct BandWalk {
+    /// Index of the first bar of the walk.
+    pub start: usize,
+    /// Index of the last bar of the walk (inclusive).
+    pub end: usize,
+    pub side: BandSide,
+}
+
+/// Fewest consecutive closes beyond a band that `band_walk` flags as a walk.
+pub const MIN_WALK_BARS: usize = 2;
 
 /// Calculate the bollinger band based on a `Vec<f32>` of price data.
 /// 
@@ -47,7 +69,7 @@ pub fn run(prices: Vec<f32>) -> Vec<(f32, f32, f32)> {
     let mut i = PERIOD;
     while {
         let ma = sma::run(prices[i-PERIOD..i].to_vec(), PERIOD);
-        let ma = match ma.get(0) {
+        let ma = match ma.first() {
             Some(&v) => v,
             None => panic!("Error calculating SMA.")
         };
@@ -72,22 +94,46 @@ pub fn run(prices: Vec<f32>) -> Vec<(f32, f32, f32)> {
     return bbs;
 }
 
-
-fn mean(data: &Vec<f32>) -> f32 {
-    let sum: f32 = data.iter().sum();
-    return sum / data.len() as f32;
-}
-
-fn std_dev(data: &Vec<f32>) -> f32 {
-    let m = mean(data);
-    let variance = data.iter().map(|d| {
-        let diff = m - *d;
-        diff * diff
-    }).sum::<f32>() / data.len() as f32;
-    return variance.sqrt();
+/// Find the runs where price "walks" a Bollinger Band: at least
+/// `MIN_WALK_BARS` consecutive closes above the upper band (or below the
+/// lower band).
+///
+/// A single close beyond a band is often a one-off event, but walking the
+/// band is a sign of a strong trend in that direction rather than of an
+/// overbought/oversold market.
+///
+/// NOTE: `prices` must be aligned with `bands` (e.g. `&prices[19..]` for
+/// `run`) and of equal length. `start` and `end` index into both.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing closing prices for a period of time
+/// * `bands` - `&[(f32, f32, f32)]` in the format of `(lower, middle, upper)` (e.g. from `run`)
+///
+/// ### Example
+/// ```
+/// bollinger_band::band_walk(&prices[19..], &bollinger_band::run(prices.to_vec()));
+/// ```
+pub fn band_walk(prices: &[f32], bands: &[(f32, f32, f32)]) -> Vec<BandWalk> {
+    if prices.len() != bands.len() { panic!("Length mismatch. `prices` contains {} entries, but `bands` contains {}.", prices.len(), bands.len()); }
+    let mut walks: Vec<BandWalk> = Vec::new();
+    let mut current: Option<(usize, BandSide)> = None;
+    for i in 0..prices.len()+1 {
+        let side = match prices.get(i) {
+            Some(&p) if p > bands[i].2 => Some(BandSide::Upper),
+            Some(&p) if p < bands[i].0 => Some(BandSide::Lower),
+            _ => None,
+        };
+        if let Some((start, walk_side)) = current {
+            if side == Some(walk_side) { continue; }
+            if i - start >= MIN_WALK_BARS {
+                walks.push(BandWalk { start: start, end: i - 1, side: walk_side });
+            }
+        }
+        current = side.map(|s| (i, s));
+    }
+    return walks;
 }
 
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -126,6 +172,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_band_walk_upper() {
+        let prices = vec![15.0, 24.0, 25.0, 26.0, 25.0, 20.0, 24.5, 15.0];
+        let bands = vec![(6.0, 15.0, 24.0); 8];
+        assert_eq!(band_walk(&prices, &bands), vec![
+            BandWalk { start: 2, end: 4, side: BandSide::Upper },
+        ]);
+    }
+
+    #[test]
+    fn test_band_walk_lower_until_end() {
+        let prices = vec![15.0, 5.0, 4.0, 20.0, 5.0, 3.0];
+        let bands = vec![(6.0, 15.0, 24.0); 6];
+        assert_eq!(band_walk(&prices, &bands), vec![
+            BandWalk { start: 1, end: 2, side: BandSide::Lower },
+            BandWalk { start: 4, end: 5, side: BandSide::Lower },
+        ]);
+    }
+
+    #[test]
+    fn test_band_walk_switches_side() {
+        let prices = vec![25.0, 26.0, 5.0, 4.0];
+        let bands = vec![(6.0, 15.0, 24.0); 4];
+        assert_eq!(band_walk(&prices, &bands), vec![
+            BandWalk { start: 0, end: 1, side: BandSide::Upper },
+            BandWalk { start: 2, end: 3, side: BandSide::Lower },
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `prices` contains 2 entries, but `bands` contains 1.")]
+    fn test_band_walk_length_mismatch() {
+        band_walk(&[10.0, 11.0], &[(6.0, 15.0, 24.0)]);
+    }
+
     #[test]
     #[should_panic(expected = "Not enough entries to calculate bollinger bands. Received 1, but required 20.")]
     fn test_run_not_enough_elements() {
@@ -0,0 +1,92 @@
+use crate::stock::ta::CrossDirection;
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Breakout {
+    pub index: usize,
+    pub direction: CrossDirection,
+}
+
+/// Find every bar of a `&[(f32, f32, f32)]` of price data that closes outside
+/// the price channel of the previous `period` bars.
+///
+/// ### Definition
+/// The Donchian channel is bounded by the highest high and lowest low of
+/// the last `period` bars. A close above the channel's upper bound is an
+/// upside breakout (`CrossDirection::Up`), and a close below its lower bound
+/// a downside breakout (`CrossDirection::Down`).
+///
+/// NOTE: The channel only covers the bars before the current one, so the
+/// first possible breakout is at `prices[period]`.
+///
+/// ### Usage
+/// The turtle traders' entry rule: go long on a close above the 20-day high
+/// and short on a close below the 20-day low.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `period` - Number of prior bars forming the channel
+///
+/// ### Example
+/// ```
+/// ta::breakouts(&prices, 20);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/d/donchianchannels.asp
+pub fn breakouts(prices: &[(f32, f32, f32)], period: usize) -> Vec<Breakout> {
+    if period == 0 { panic!("Invalid period for breakouts. Received 0, but required at least 1."); }
+    let mut breakouts: Vec<Breakout> = Vec::new();
+    for i in period..prices.len() {
+        let mut high = f32::MIN;
+        let mut low = f32::MAX;
+        for p in prices[i-period..i].iter() {
+            if p.2 > high { high = p.2; }
+            if p.1 < low { low = p.1; }
+        }
+        let close = prices[i].0;
+        if close > high {
+            breakouts.push(Breakout { index: i, direction: CrossDirection::Up });
+        } else if close < low {
+            breakouts.push(Breakout { index: i, direction: CrossDirection::Down });
+        }
+    }
+    return breakouts;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_breakouts_upside() {
+        let prices = vec![
+            (10.0, 9.0, 11.0), (10.5, 9.5, 11.5),
+            (10.0, 9.0, 11.0), (10.5, 9.5, 11.5),
+            (10.0, 9.0, 11.0), (12.0, 10.0, 12.5),
+            (11.8, 11.0, 12.4),
+        ];
+        assert_eq!(breakouts(&prices, 3), vec![Breakout { index: 5, direction: CrossDirection::Up }]);
+    }
+
+    #[test]
+    fn test_breakouts_downside() {
+        let prices = vec![
+            (10.0, 9.0, 11.0), (10.5, 9.5, 11.5),
+            (10.0, 9.0, 11.0), (8.5, 8.0, 10.0),
+        ];
+        assert_eq!(breakouts(&prices, 3), vec![Breakout { index: 3, direction: CrossDirection::Down }]);
+    }
+
+    #[test]
+    fn test_breakouts_not_enough_elements() {
+        assert_eq!(breakouts(&[(10.0, 9.0, 11.0)], 3), vec![]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid period for breakouts. Received 0, but required at least 1.")]
+    fn test_breakouts_zero_period() {
+        breakouts(&[(10.0, 9.0, 11.0)], 0);
+    }
+}
@@ -0,0 +1,107 @@
+use crate::stock::ta::{sma, PriceSource};
+
+/// Calculate the commodity channel index (CCI) based on a
+/// `Vec<(f32, f32, f32)>` of price data, using the typical price.
+///
+/// ### Definition
+/// An unbounded oscillator measuring how far the current price has moved
+/// from its moving average, relative to the average deviation from that
+/// moving average. Roughly 70-80% of values fall between -100 and 100.
+///
+/// ### Formula
+/// `cci = (tp - sma(tp, n)) / (0.015 * md)`
+///
+/// `md = sum(|tp_i - sma(tp, n)|) / n`
+///
+/// - `tp`: typical price `(h + l + c) / 3` of a bar
+/// - `md`: mean deviation of the last `n` typical prices
+/// - `n`: number of periods (commonly 20)
+///
+/// NOTE: When all prices in the window are equal the mean deviation is 0,
+/// in which case the CCI is 0.
+///
+/// ### Usage
+/// A move above 100 can signal the start of an uptrend (or overbought
+/// conditions) and a move below -100 the start of a downtrend (or oversold
+/// conditions).
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of periods to average
+///
+/// ### Example
+/// ```
+/// cci::run(prices, 20);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/c/commoditychannelindex.asp
+pub fn run(prices: Vec<(f32, f32, f32)>, period: usize) -> Vec<f32> {
+    return run_with_source(prices, period, PriceSource::Typical);
+}
+
+/// Calculate the CCI using `source` in place of the typical price.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of periods to average
+/// * `source` - Price of each bar fed into the CCI
+///
+/// ### Example
+/// ```
+/// cci::run_with_source(prices, 20, PriceSource::Close);
+/// ```
+pub fn run_with_source(prices: Vec<(f32, f32, f32)>, period: usize, source: PriceSource) -> Vec<f32> {
+    if period == 0 || prices.len() < period { panic!("Not enough entries to calculate the CCI. Received {}, but required {}.", prices.len(), period); }
+    let values: Vec<f32> = prices.iter().map(|&p| source.price(p)).collect();
+    let smas = sma::run(values.to_vec(), period);
+    let mut ccis: Vec<f32> = Vec::new();
+    for (i, avg) in smas.iter().enumerate() {
+        let window = &values[i..i+period];
+        let md = window.iter().map(|v| (v - avg).abs()).sum::<f32>() / period as f32;
+        if md == 0.0 {
+            ccis.push(0.0);
+        } else {
+            ccis.push((window[period-1] - avg) / (0.015 * md));
+        }
+    }
+    return ccis;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        // sma = 12, md = 4/3
+        let prices = vec![(10.0, 10.0, 10.0), (12.0, 12.0, 12.0), (14.0, 14.0, 14.0)];
+        assert_eq!(run(prices, 3), vec![100.0]);
+    }
+
+    #[test]
+    fn test_run_flat() {
+        let prices = vec![(10.0, 9.0, 11.0), (10.0, 9.0, 11.0), (10.0, 9.0, 11.0)];
+        assert_eq!(run(prices, 3), vec![0.0]);
+    }
+
+    #[test]
+    fn test_run_with_source() {
+        let prices = [
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+        ];
+        let typical = run(prices.to_vec(), 3);
+        assert_eq!(run_with_source(prices.to_vec(), 3, PriceSource::Typical), typical);
+        assert_ne!(run_with_source(prices.to_vec(), 3, PriceSource::Close), typical);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the CCI. Received 1, but required 20.")]
+    fn test_run_not_enough_elements() {
+        run(vec![(10.0, 10.0, 10.0)], 20);
+    }
+}
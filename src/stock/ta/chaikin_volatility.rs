@@ -0,0 +1,68 @@
+use crate::stock::ta::{ema, roc};
+
+/// Calculate the Chaikin Volatility based on a `Vec<(f32, f32, f32)>` of
+/// price data.
+///
+/// ### Definition
+/// The rate of change of an EMA of each bar's high-low range, measuring
+/// how quickly the trading range is widening or narrowing.
+///
+/// ### Formula
+/// `range = h - l`
+///
+/// `cv = roc(ema(range, n), m)`
+///
+/// - `n`: number of periods of the EMA (commonly 10)
+/// - `m`: number of periods of the rate of change (commonly 10)
+///
+/// NOTE: The first value corresponds to `prices[n + m]`.
+///
+/// ### Usage
+/// Rising Chaikin Volatility warns of an increasing range, which tends to
+/// occur near market tops driven by nervous selling. Falling volatility
+/// shows a quiet market, as during a maturing consolidation.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `ema_period` - Number of periods of the EMA of the range
+/// * `roc_period` - Number of periods of the rate of change
+///
+/// ### Example
+/// ```
+/// chaikin_volatility::run(prices, 10, 10);
+/// ```
+pub fn run(prices: Vec<(f32, f32, f32)>, ema_period: usize, roc_period: usize) -> Vec<f32> {
+    let required = ema_period + roc_period + 1;
+    if prices.len() < required { panic!("Not enough entries to calculate the Chaikin Volatility. Received {}, but required {} (ema_period+roc_period+1).", prices.len(), required); }
+    let ranges: Vec<f32> = prices.iter().map(|&(_, low, high)| high - low).collect();
+    return roc(&ema::run(ranges, ema_period), roc_period);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_widening_range() {
+        let prices: Vec<(f32, f32, f32)> = (0..12).map(|i| {
+            let half = 1.0 + 0.5 * i as f32;
+            (100.0, 100.0 - half, 100.0 + half)
+        }).collect();
+        let cvs = run(prices, 3, 2);
+        assert_eq!(cvs.len(), 12 - 5);
+        assert_eq!(cvs.iter().all(|&v| v > 0.0), true);
+    }
+
+    #[test]
+    fn test_run_constant_range() {
+        let prices = vec![(10.0, 9.0, 11.0); 8];
+        assert_eq!(run(prices, 3, 2), vec![0.0; 3]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the Chaikin Volatility. Received 5, but required 6 (ema_period+roc_period+1).")]
+    fn test_run_not_enough_elements() {
+        run(vec![(10.0, 9.0, 11.0); 5], 3, 2);
+    }
+}
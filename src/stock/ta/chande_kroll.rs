@@ -0,0 +1,124 @@
+use crate::stock::ta::atr;
+use crate::stock::ta::util::{RollingMax, RollingMin};
+
+/// Calculate the Chande Kroll Stop based on a `Vec<(f32, f32, f32)>` of
+/// price data.
+///
+/// ### Definition
+/// A trailing stop built from the ATR. Preliminary stops are placed a
+/// multiple of the ATR away from the recent extreme, then the stops
+/// themselves are smoothed with a rolling extreme so they only move in the
+/// direction of the trend.
+///
+/// ### Formula
+/// `prelim_long = highest(h, p) - (x * atr(p))`
+///
+/// `prelim_short = lowest(l, p) + (x * atr(p))`
+///
+/// `long_stop = highest(prelim_long, q)`
+///
+/// `short_stop = lowest(prelim_short, q)`
+///
+/// - `p`: ATR period (commonly 10)
+/// - `x`: ATR multiplier (commonly 1)
+/// - `q`: stop period (commonly 9)
+///
+/// NOTE: The first values correspond to `prices[p + q - 1]`.
+///
+/// ### Usage
+/// The long stop trails below price in an uptrend and the short stop above
+/// price in a downtrend. A close below the long stop exits longs, and a
+/// close above the short stop exits shorts. Price above both lines is
+/// bullish, below both bearish.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `atr_period` - Number of periods for the ATR and the high/low window
+/// * `atr_mult` - Number of ATRs between the extreme and the preliminary stop
+/// * `stop_period` - Number of preliminary stops in the rolling extreme
+///
+/// # Returns
+/// `(Vec<f32>, Vec<f32>)` in the form of `(long_stop, short_stop)`.
+///
+/// ### Example
+/// ```
+/// chande_kroll::run(prices, 10, 1.0, 9);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/articles/trading/08/atr.asp
+pub fn run(prices: Vec<(f32, f32, f32)>, atr_period: usize, atr_mult: f32, stop_period: usize) -> (Vec<f32>, Vec<f32>) {
+    if stop_period == 0 { panic!("Invalid stop period for Chande Kroll Stop. Received 0, but required at least 1."); }
+    if atr_period == 0 || prices.len() < atr_period + stop_period { panic!("Not enough entries to calculate the Chande Kroll Stop. Received {}, but required {} (atr_period+stop_period).", prices.len(), atr_period + stop_period); }
+    let atrs = atr::run(prices.to_vec(), atr_period);
+
+    // Preliminary stops from the high/low window ending on each ATR bar
+    let mut highs = RollingMax::new(atr_period);
+    let mut lows = RollingMin::new(atr_period);
+    let mut long_stops = RollingMax::new(stop_period);
+    let mut short_stops = RollingMin::new(stop_period);
+    let mut long: Vec<f32> = Vec::new();
+    let mut short: Vec<f32> = Vec::new();
+    for (i, &(_, low, high)) in prices.iter().enumerate() {
+        highs.push(high);
+        lows.push(low);
+        if i < atr_period { continue; }
+        let atr = atrs[i - atr_period];
+        let (highest, lowest) = match (highs.value(), lows.value()) {
+            (Some(h), Some(l)) => (h, l),
+            _ => panic!("Could not get entry in `prices`."),
+        };
+        long_stops.push(highest - (atr_mult * atr));
+        short_stops.push(lowest + (atr_mult * atr));
+        if i < atr_period + stop_period - 1 { continue; }
+        match (long_stops.value(), short_stops.value()) {
+            (Some(l), Some(s)) => {
+                long.push(l);
+                short.push(s);
+            },
+            _ => panic!("Could not get preliminary stop."),
+        };
+    }
+    return (long, short);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        // True ranges are all 2, so the ATR is 2
+        let prices = vec![(10.0, 9.0, 11.0), (11.0, 10.0, 12.0), (12.0, 11.0, 13.0), (13.0, 12.0, 14.0)];
+        let (long, short) = run(prices, 2, 1.0, 2);
+        // prelim long: [13 - 2, 14 - 2], prelim short: [10 + 2, 11 + 2]
+        assert_eq!(long, vec![12.0]);
+        assert_eq!(short, vec![12.0]);
+    }
+
+    #[test]
+    fn test_run_uptrend_long_stop_below_price() {
+        let prices: Vec<(f32, f32, f32)> = (0..40).map(|i| {
+            let c = 100.0 + (i as f32) * 1.5 + if i % 3 == 0 { -1.0 } else { 0.5 };
+            (c, c - 1.2, c + 1.0)
+        }).collect();
+        let (long, short) = run(prices.to_vec(), 10, 1.0, 9);
+        assert_eq!(long.len(), prices.len() - 18);
+        assert_eq!(short.len(), long.len());
+        for k in 0..long.len() {
+            let (close, _, _) = prices[18 + k];
+            assert_eq!(long[k] < close, true);
+        }
+        // The long stop only trails upwards
+        for k in 1..long.len() {
+            assert_eq!(long[k] >= long[k-1], true);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the Chande Kroll Stop. Received 4, but required 19 (atr_period+stop_period).")]
+    fn test_run_not_enough_elements() {
+        run(vec![(10.0, 9.0, 11.0); 4], 10, 1.0, 9);
+    }
+}
@@ -0,0 +1,201 @@
+use crate::stock::ta::{alignment, GapPolicy};
+
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum CrossDirection {
+    /// Crossed from below to above.
+    Up,
+    /// Crossed from above to below.
+    Down,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Crossover {
+    pub index: usize,
+    pub direction: CrossDirection,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct ZeroCross {
+    pub index: usize,
+    pub direction: CrossDirection,
+}
+
+/// Find every index where series `a` crosses series `b`.
+///
+/// A cross up is flagged when `a` moves from at or below `b` to above it,
+/// and a cross down when `a` moves from at or above `b` to below it. The
+/// `index` is the first bar on the new side.
+///
+/// NOTE: Both series must be aligned (e.g. a 50-day SMA trimmed to line up
+/// with a 200-day SMA) and of equal length.
+///
+/// # Arguments
+/// * `a` - `&[f32]` containing the series doing the crossing (e.g. the fast SMA)
+/// * `b` - `&[f32]` containing the series being crossed (e.g. the slow SMA)
+///
+/// ### Example
+/// ```
+/// ta::crossovers(&sma50[150..], &sma200);
+/// ```
+pub fn crossovers(a: &[f32], b: &[f32]) -> Vec<Crossover> {
+    if a.len() != b.len() { panic!("Length mismatch. `a` contains {} entries, but `b` contains {}.", a.len(), b.len()); }
+    let mut crosses: Vec<Crossover> = Vec::new();
+    for i in 1..a.len() {
+        if a[i-1] <= b[i-1] && a[i] > b[i] {
+            crosses.push(Crossover { index: i, direction: CrossDirection::Up });
+        } else if a[i-1] >= b[i-1] && a[i] < b[i] {
+            crosses.push(Crossover { index: i, direction: CrossDirection::Down });
+        }
+    }
+    return crosses;
+}
+
+/// Find every index where aligned series `a` crosses series `b` when their
+/// warm-ups differ (e.g. padded with `align`).
+///
+/// NOTE: A bar is a gap when either input is `None` or `NaN`. With
+/// `GapPolicy::PropagateNone` a cross is only flagged between two
+/// consecutive bars without a gap. With `GapPolicy::SkipBar` gap bars are
+/// skipped and each bar is compared with the last bar without one. The
+/// `index` is always the bar in the inputs.
+///
+/// # Arguments
+/// * `a` - `&[Option<f32>]` containing the series doing the crossing
+/// * `b` - `&[Option<f32>]` containing the series being crossed
+/// * `policy` - How bars with a gap in either input are treated
+///
+/// ### Example
+/// ```
+/// ta::crossovers_aligned(&fast, &slow, GapPolicy::SkipBar);
+/// ```
+pub fn crossovers_aligned(a: &[Option<f32>], b: &[Option<f32>], policy: GapPolicy) -> Vec<Crossover> {
+    if a.len() != b.len() { panic!("Length mismatch. `a` contains {} entries, but `b` contains {}.", a.len(), b.len()); }
+    let mut crosses: Vec<Crossover> = Vec::new();
+    // Last bar without a gap, as `(a, b)`
+    let mut prev: Option<(f32, f32)> = None;
+    for i in 0..a.len() {
+        let (x, y) = match (alignment::present(a[i]), alignment::present(b[i])) {
+            (Some(x), Some(y)) => (x, y),
+            _ => {
+                if policy == GapPolicy::PropagateNone { prev = None; }
+                continue;
+            },
+        };
+        if let Some((px, py)) = prev {
+            if px <= py && x > y {
+                crosses.push(Crossover { index: i, direction: CrossDirection::Up });
+            } else if px >= py && x < y {
+                crosses.push(Crossover { index: i, direction: CrossDirection::Down });
+            }
+        }
+        prev = Some((x, y));
+    }
+    return crosses;
+}
+
+/// Find every index where a `&[f32]` of oscillator values crosses zero.
+///
+/// Works with any zero-centered oscillator (e.g. the MACD, TRIX, Awesome
+/// Oscillator or momentum), where a cross above zero is bullish and a cross
+/// below zero bearish. The `index` is the first bar on the new side.
+///
+/// NOTE: Readings of exactly zero do not belong to either side, so touching
+/// zero and returning to the same side is not a cross, while passing
+/// through a zero reading to the other side is reported once.
+///
+/// # Arguments
+/// * `values` - `&[f32]` containing oscillator values
+///
+/// ### Example
+/// ```
+/// ta::zero_crosses(&macd);
+/// ```
+pub fn zero_crosses(values: &[f32]) -> Vec<ZeroCross> {
+    let mut crosses: Vec<ZeroCross> = Vec::new();
+    // Whether the last non-zero reading was positive
+    let mut above: Option<bool> = None;
+    for (i, &v) in values.iter().enumerate() {
+        if v == 0.0 || v.is_nan() { continue; }
+        let now_above = v > 0.0;
+        match above {
+            Some(false) if now_above => crosses.push(ZeroCross { index: i, direction: CrossDirection::Up }),
+            Some(true) if !now_above => crosses.push(ZeroCross { index: i, direction: CrossDirection::Down }),
+            _ => {},
+        };
+        above = Some(now_above);
+    }
+    return crosses;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_crossovers() {
+        let a = vec![1.0, 2.0, 4.0, 5.0, 3.0, 2.0];
+        let b = vec![3.0, 3.0, 3.0, 3.0, 3.0, 3.0];
+        assert_eq!(
+            crossovers(&a, &b),
+            vec![
+                Crossover { index: 2, direction: CrossDirection::Up },
+                Crossover { index: 5, direction: CrossDirection::Down },
+            ]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `a` contains 2 entries, but `b` contains 1.")]
+    fn test_crossovers_length_mismatch() {
+        crossovers(&[1.0, 2.0], &[1.0]);
+    }
+
+    #[test]
+    fn test_crossovers_aligned_mismatched_warmups() {
+        // `b` warms up a bar later than `a`, and `a` drops below `b` across a gap
+        let a = vec![None, Some(1.0), Some(4.0), None, Some(2.0), Some(5.0)];
+        let b = vec![None, None, Some(3.0), Some(3.0), Some(3.0), Some(3.0)];
+        assert_eq!(
+            crossovers_aligned(&a, &b, GapPolicy::PropagateNone),
+            vec![Crossover { index: 5, direction: CrossDirection::Up }]
+        );
+        assert_eq!(
+            crossovers_aligned(&a, &b, GapPolicy::SkipBar),
+            vec![
+                Crossover { index: 4, direction: CrossDirection::Down },
+                Crossover { index: 5, direction: CrossDirection::Up },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_crossovers_aligned_matches_crossovers() {
+        let a = vec![1.0, 2.0, 4.0, 5.0, 3.0, 2.0];
+        let b = vec![3.0, 3.0, 3.0, 3.0, 3.0, 3.0];
+        let a_aligned: Vec<Option<f32>> = a.iter().map(|&v| Some(v)).collect();
+        let b_aligned: Vec<Option<f32>> = b.iter().map(|&v| Some(v)).collect();
+        assert_eq!(crossovers_aligned(&a_aligned, &b_aligned, GapPolicy::SkipBar), crossovers(&a, &b));
+    }
+
+    #[test]
+    fn test_zero_crosses() {
+        let values = vec![-1.5, -0.2, 0.4, 1.2, 0.3, -0.1, -0.8];
+        assert_eq!(
+            zero_crosses(&values),
+            vec![
+                ZeroCross { index: 2, direction: CrossDirection::Up },
+                ZeroCross { index: 5, direction: CrossDirection::Down },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_zero_crosses_touching_zero() {
+        assert_eq!(zero_crosses(&[1.0, 0.0, 1.0]), vec![]);
+        assert_eq!(zero_crosses(&[1.0, 0.0, -1.0]), vec![ZeroCross { index: 2, direction: CrossDirection::Down }]);
+        assert_eq!(zero_crosses(&[]), vec![]);
+    }
+}
@@ -0,0 +1,49 @@
+use crate::stock::ta::{stochastic_oscillator, williams_r};
+
+/// Largest difference allowed between two implementations of the same value.
+const TOLERANCE: f32 = 1e-3;
+
+/// Check the identity `%R = %K - 100` between `williams_r::run` and
+/// `stochastic_oscillator::run` on a `Vec<(f32, f32, f32)>` of price data.
+///
+/// Both indicators use the same high/low window, so any difference beyond
+/// rounding means one of the implementations changed. Useful as a guard
+/// after modifying either of them.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of trading sessions in the high/low window
+///
+/// ### Example
+/// ```
+/// assert!(cross_check::williams_vs_stoch(prices, 14));
+/// ```
+pub fn williams_vs_stoch(prices: Vec<(f32, f32, f32)>, period: usize) -> bool {
+    let rs = williams_r::run(prices.to_vec(), period);
+    let ks = stochastic_oscillator::run(prices, period);
+    if rs.len() != ks.len() { return false; }
+    return rs.iter().zip(ks.iter()).all(|(r, k)| (r - (k - 100.0)).abs() <= TOLERANCE);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_williams_vs_stoch_complex() {
+        let prices = vec![
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+        ];
+        assert_eq!(williams_vs_stoch(prices.to_vec(), 14), true);
+        assert_eq!(williams_vs_stoch(prices, 5), true);
+    }
+}
@@ -0,0 +1,72 @@
+/// Calculate a time-decayed average of a `&[f32]` of price data, where the
+/// weight of each past price halves every `half_life` bars.
+///
+/// ### Formula
+/// `d = 0.5 ^ (1 / h)`
+///
+/// `avg = sum(p_k * d^k) / sum(d^k)`
+///
+/// - `p_k`: price `k` bars ago
+/// - `h`: half-life in bars
+///
+/// ### Relationship to the EMA
+/// This is an EMA with a smoothing coefficient of `s = 1 - d`, i.e. an EMA
+/// period of `p = (2 / s) - 1`. A half-life of ~6.6 bars is roughly a
+/// 20-period EMA. Unlike `ema::run`, there is no SMA seed: the weights are
+/// normalized by their sum, so every bar (including the first) has a value.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `half_life` - Number of bars after which a price's weight has halved
+///
+/// ### Example
+/// ```
+/// ta::decayed_average(&prices, 10.0);
+/// ```
+pub fn decayed_average(prices: &[f32], half_life: f32) -> Vec<f32> {
+    if half_life.is_nan() || half_life <= 0.0 { panic!("Invalid half-life. Received {}, but required a value greater than 0.", half_life); }
+    let decay = 0.5f64.powf(1.0 / half_life as f64);
+    let mut averages: Vec<f32> = Vec::new();
+    let mut weighted_sum: f64 = 0.0;
+    let mut weight_total: f64 = 0.0;
+    for &p in prices {
+        weighted_sum = (weighted_sum * decay) + p as f64;
+        weight_total = (weight_total * decay) + 1.0;
+        averages.push((weighted_sum / weight_total) as f32);
+    }
+    return averages;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::ta::stats::mean;
+
+    #[test]
+    fn test_decayed_average_half_life() {
+        // The previous price carries half the weight of the current one
+        assert_eq!(decayed_average(&[10.0, 40.0], 1.0), vec![10.0, 30.0]);
+    }
+
+    #[test]
+    fn test_decayed_average_large_half_life() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let averages = decayed_average(&prices, 1e6);
+        assert_eq!((averages[7] - mean(&prices)).abs() < 1e-3, true);
+    }
+
+    #[test]
+    fn test_decayed_average_small_half_life() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let averages = decayed_average(&prices, 0.01);
+        for i in 0..prices.len() {
+            assert_eq!((averages[i] - prices[i]).abs() < 1e-3, true);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid half-life. Received 0, but required a value greater than 0.")]
+    fn test_decayed_average_zero_half_life() {
+        decayed_average(&[10.0], 0.0);
+    }
+}
@@ -0,0 +1,113 @@
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum DivergenceKind {
+    /// Price makes a lower low while the indicator makes a higher low.
+    Bullish,
+    /// Price makes a higher high while the indicator makes a lower high.
+    Bearish,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Divergence {
+    /// Index of the earlier swing.
+    pub first: usize,
+    /// Index of the later swing, where the divergence is confirmed.
+    pub second: usize,
+    pub kind: DivergenceKind,
+}
+
+/// Number of bars on each side a swing high/low must exceed.
+pub const SWING_BARS: usize = 2;
+
+/// Find the divergences between a `&[f32]` of price data and a `&[f32]` of
+/// indicator values (e.g. the RSI, MACD or OBV).
+///
+/// ### Definition
+/// A swing high (low) is a price greater (less) than the `SWING_BARS` prices
+/// on either side of it. Each pair of consecutive swing highs is compared
+/// with the indicator at the same bars: a higher high in price with a lower
+/// high in the indicator is a bearish divergence. Likewise, a lower low in
+/// price with a higher low in the indicator is a bullish divergence.
+///
+/// NOTE: Both series must be aligned and of equal length. A swing can only
+/// be confirmed `SWING_BARS` bars after it forms.
+///
+/// ### Usage
+/// Divergence shows momentum fading against the price trend and is often an
+/// early sign of a reversal. It is best confirmed by the price itself (e.g.
+/// a break of the last swing).
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `indicator` - `&[f32]` containing indicator values aligned with `prices`
+///
+/// ### Example
+/// ```
+/// ta::divergences(&prices[14..], &rsi::run(prices.to_vec(), 14));
+/// ```
+pub fn divergences(prices: &[f32], indicator: &[f32]) -> Vec<Divergence> {
+    if prices.len() != indicator.len() { panic!("Length mismatch. `prices` contains {} entries, but `indicator` contains {}.", prices.len(), indicator.len()); }
+    let mut divergences: Vec<Divergence> = Vec::new();
+    if prices.len() < 2 * SWING_BARS + 1 { return divergences; }
+
+    let mut last_high: Option<usize> = None;
+    let mut last_low: Option<usize> = None;
+    for i in SWING_BARS..prices.len()-SWING_BARS {
+        let neighbours: Vec<f32> = (i-SWING_BARS..i+SWING_BARS+1).filter(|&j| j != i).map(|j| prices[j]).collect();
+        if neighbours.iter().all(|&p| p < prices[i]) {
+            if let Some(prev) = last_high {
+                if prices[i] > prices[prev] && indicator[i] < indicator[prev] {
+                    divergences.push(Divergence { first: prev, second: i, kind: DivergenceKind::Bearish });
+                }
+            }
+            last_high = Some(i);
+        }
+        if neighbours.iter().all(|&p| p > prices[i]) {
+            if let Some(prev) = last_low {
+                if prices[i] < prices[prev] && indicator[i] > indicator[prev] {
+                    divergences.push(Divergence { first: prev, second: i, kind: DivergenceKind::Bullish });
+                }
+            }
+            last_low = Some(i);
+        }
+    }
+    return divergences;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_divergences_bearish() {
+        let prices = vec![10.0, 11.0, 14.0, 12.0, 11.0, 12.0, 13.0, 15.0, 13.0, 12.0];
+        let indicator = vec![50.0, 60.0, 75.0, 60.0, 55.0, 58.0, 62.0, 68.0, 60.0, 55.0];
+        assert_eq!(divergences(&prices, &indicator), vec![
+            Divergence { first: 2, second: 7, kind: DivergenceKind::Bearish },
+        ]);
+    }
+
+    #[test]
+    fn test_divergences_bullish() {
+        let prices = vec![15.0, 14.0, 11.0, 13.0, 14.0, 13.0, 12.0, 10.0, 12.0, 13.0];
+        let indicator = vec![50.0, 40.0, 25.0, 40.0, 45.0, 42.0, 38.0, 32.0, 40.0, 45.0];
+        assert_eq!(divergences(&prices, &indicator), vec![
+            Divergence { first: 2, second: 7, kind: DivergenceKind::Bullish },
+        ]);
+    }
+
+    #[test]
+    fn test_divergences_confirmed_trend() {
+        // The indicator makes a higher high along with price
+        let prices = vec![10.0, 11.0, 14.0, 12.0, 11.0, 12.0, 13.0, 15.0, 13.0, 12.0];
+        let indicator = vec![50.0, 60.0, 65.0, 60.0, 55.0, 58.0, 62.0, 68.0, 60.0, 55.0];
+        assert_eq!(divergences(&prices, &indicator), Vec::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `prices` contains 2 entries, but `indicator` contains 1.")]
+    fn test_divergences_length_mismatch() {
+        divergences(&[10.0, 11.0], &[50.0]);
+    }
+}
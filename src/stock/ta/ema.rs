@@ -59,6 +59,84 @@ pub fn run(prices: Vec<f32>, periods: usize) -> Vec<f32> {
     return emas;
 }
 
+/// Calculate the EMA, emitting approximate values before the SMA seed is
+/// available once at least `min_periods` prices have been seen.
+///
+/// Until `periods` prices are available the value is the average of the
+/// prices seen so far. The value at `prices[periods - 1]` is the SMA seed,
+/// and from `prices[periods]` onwards the values are identical to `run`.
+/// The first value corresponds to `prices[min_periods - 1]`.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `periods` - Number of periods to average
+/// * `min_periods` - Number of prices required before emitting a value (`1..=periods`)
+///
+/// ### Example
+/// ```
+/// ema::run_min_periods(prices, 200, 20);
+/// ```
+pub fn run_min_periods(prices: Vec<f32>, periods: usize, min_periods: usize) -> Vec<f32> {
+    if min_periods == 0 || min_periods > periods { panic!("Invalid minimum periods. Received {}, but required a value between 1 and {}.", min_periods, periods); }
+    if prices.len() < min_periods { panic!("Not enough entries to calculate the EMA. Received {}, but required {}.", prices.len(), min_periods); }
+    let mut emas: Vec<f32> = Vec::new();
+    for i in min_periods..periods.min(prices.len())+1 {
+        emas.push(prices[0..i].iter().sum::<f32>() / i as f32);
+    }
+    if prices.len() > periods { emas.extend(run(prices, periods)); }
+    return emas;
+}
+
+/// Incremental EMA for live data, producing the same values as `run` one
+/// price at a time in O(1) per update.
+///
+/// ### Example
+/// ```
+/// let mut state = ema::EmaState::new(20);
+/// for price in prices {
+///     if let Some(ema) = state.update(price) { ... }
+/// }
+/// ```
+pub struct EmaState {
+    periods: usize,
+    smoothing: f32,
+    seen: usize,
+    // Summed in `f64` like `sma::run`, so the seed matches `run`'s
+    sum: f64,
+    ema_prev: Option<f32>,
+}
+
+impl EmaState {
+    pub fn new(periods: usize) -> Self {
+        if periods == 0 { panic!("Invalid period for EMA. Received 0, but required at least 1."); }
+        return Self {
+            periods: periods,
+            smoothing: 2.0 / (periods as f32 + 1.0),
+            seen: 0,
+            sum: 0.0,
+            ema_prev: None,
+        };
+    }
+
+    /// Feed the next price, returning the EMA once `periods+1` prices have
+    /// been seen (the SMA seed is not emitted, like `run`).
+    pub fn update(&mut self, price: f32) -> Option<f32> {
+        match self.ema_prev {
+            Some(ema_prev) => {
+                let ema = (price * self.smoothing) + (ema_prev * (1.0 - self.smoothing));
+                self.ema_prev = Some(ema);
+                return Some(ema);
+            },
+            None => {
+                self.sum += price as f64;
+                self.seen += 1;
+                if self.seen == self.periods { self.ema_prev = Some((self.sum as f32) / self.periods as f32); }
+                return None;
+            },
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -86,4 +164,22 @@ mod tests {
     fn test_run_not_enough_elements() {
         run(vec![10.0, 10.0, 15.0, 20.0, 20.0], 5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_run_min_periods() {
+        let prices = [10.0, 10.0, 15.0, 20.0, 20.0, 10.0, 10.0, 10.0];
+        let partial = run_min_periods(prices.to_vec(), 5, 3);
+        // Expanding averages, the SMA seed, then the regular EMA
+        assert_eq!(partial, vec![11.666667, 13.75, 15.0, 13.333332, 12.222221, 11.48148]);
+        assert_eq!(partial[3..].to_vec(), run(prices.to_vec(), 5));
+        assert_eq!(run_min_periods(vec![10.0, 10.0, 15.0], 5, 2), vec![10.0, 11.666667]);
+    }
+
+    #[test]
+    fn test_ema_state_matches_run() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let mut state = EmaState::new(5);
+        let streamed: Vec<f32> = prices.iter().filter_map(|&p| state.update(p)).collect();
+        assert_eq!(streamed, run(prices, 5));
+    }
+}
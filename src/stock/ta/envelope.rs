@@ -0,0 +1,76 @@
+/// Track the peak/trough envelope of a `&[f32]` of values.
+///
+/// The upper line jumps up to every new high and otherwise decays towards
+/// the values by `decay` per bar. The lower line mirrors it for lows. This
+/// is the classic peak detector of signal processing.
+///
+/// ### Formula
+/// `upper = max(v, upper_prev - decay)`
+///
+/// `lower = min(v, lower_prev + decay)`
+///
+/// NOTE: Both lines start at the first value and the output is as long as
+/// `prices`.
+///
+/// ### Usage
+/// Applied to an oscillator, the envelope gives adaptive overbought/oversold
+/// thresholds: a reading near the upper line is extreme compared to the
+/// oscillator's recent range rather than a fixed level.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices or indicator values for a period of time
+/// * `decay` - Amount each line moves back towards the values per bar
+///
+/// # Returns
+/// `(Vec<f32>, Vec<f32>)` in the form of `(upper, lower)`.
+///
+/// ### Example
+/// ```
+/// let (upper, lower) = ta::envelope(&rsi::run(prices, 14), 0.5);
+/// ```
+pub fn envelope(prices: &[f32], decay: f32) -> (Vec<f32>, Vec<f32>) {
+    if decay < 0.0 { panic!("Invalid decay. Received {}, but required a value of at least 0.", decay); }
+    let mut upper: Vec<f32> = Vec::new();
+    let mut lower: Vec<f32> = Vec::new();
+    for (i, &p) in prices.iter().enumerate() {
+        if i == 0 {
+            upper.push(p);
+            lower.push(p);
+            continue;
+        }
+        upper.push(p.max(upper[i-1] - decay));
+        lower.push(p.min(lower[i-1] + decay));
+    }
+    return (upper, lower);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_envelope_spike_decays() {
+        let prices = vec![10.0, 10.0, 20.0, 10.0, 10.0, 10.0, 10.0];
+        let (upper, lower) = envelope(&prices, 4.0);
+        assert_eq!(upper, vec![10.0, 10.0, 20.0, 16.0, 12.0, 10.0, 10.0]);
+        assert_eq!(lower, vec![10.0, 10.0, 14.0, 10.0, 10.0, 10.0, 10.0]);
+    }
+
+    #[test]
+    fn test_envelope_trough() {
+        let (upper, lower) = envelope(&[50.0, 20.0, 50.0, 50.0], 10.0);
+        assert_eq!(upper, vec![50.0, 40.0, 50.0, 50.0]);
+        assert_eq!(lower, vec![50.0, 20.0, 30.0, 40.0]);
+    }
+
+    #[test]
+    fn test_envelope_empty() {
+        assert_eq!(envelope(&[], 1.0), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid decay. Received -1, but required a value of at least 0.")]
+    fn test_envelope_negative_decay() {
+        envelope(&[10.0], -1.0);
+    }
+}
@@ -0,0 +1,94 @@
+use crate::stock::historical::Candle;
+
+/// Project a `&[Candle]` down to its closing prices, the input most
+/// indicators (e.g. `rsi::run`) expect.
+///
+/// ### Example
+/// ```
+/// rsi::run(ta::closes(stock.get_historical_data()), 14);
+/// ```
+pub fn closes(candles: &[Candle]) -> Vec<f32> {
+    return candles.iter().map(|c| c.get_close()).collect();
+}
+
+/// Project a `&[Candle]` down to its high prices.
+pub fn highs(candles: &[Candle]) -> Vec<f32> {
+    return candles.iter().map(|c| c.get_high()).collect();
+}
+
+/// Project a `&[Candle]` down to its low prices.
+pub fn lows(candles: &[Candle]) -> Vec<f32> {
+    return candles.iter().map(|c| c.get_low()).collect();
+}
+
+/// Project a `&[Candle]` down to its opening prices.
+pub fn opens(candles: &[Candle]) -> Vec<f32> {
+    return candles.iter().map(|c| c.get_open()).collect();
+}
+
+/// Project a `&[Candle]` down to its median prices, `(high + low) / 2`.
+///
+/// ### Example
+/// ```
+/// alligator::run(ta::medians(stock.get_historical_data()));
+/// ```
+pub fn medians(candles: &[Candle]) -> Vec<f32> {
+    return candles.iter().map(|c| (c.get_high() + c.get_low()) / 2.0).collect();
+}
+
+/// Project a `&[Candle]` down to `(close, low, high)` tuples, the layout
+/// the bar-based indicators (e.g. `stochastic_oscillator::run`) expect.
+///
+/// Prefer this over building the tuples by hand: data sources commonly use
+/// `(high, low, close)` and a swapped field still produces plausible,
+/// subtly wrong results.
+///
+/// ### Example
+/// ```
+/// stochastic_oscillator::run(ta::close_low_highs(stock.get_historical_data()), 14);
+/// ```
+pub fn close_low_highs(candles: &[Candle]) -> Vec<(f32, f32, f32)> {
+    return candles.iter().map(|c| (c.get_close(), c.get_low(), c.get_high())).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candles() -> Vec<Candle> {
+        return vec![
+            Candle::new("4/22/2021 16:00:00", 10.0, 17.0, 8.0, 11.0, 10050),
+            Candle::new("4/23/2021 16:00:00", 11.0, 18.0, 9.0, 12.0, 10070),
+        ];
+    }
+
+    #[test]
+    fn test_closes() {
+        assert_eq!(closes(&candles()), vec![11.0, 12.0]);
+    }
+
+    #[test]
+    fn test_highs() {
+        assert_eq!(highs(&candles()), vec![17.0, 18.0]);
+    }
+
+    #[test]
+    fn test_lows() {
+        assert_eq!(lows(&candles()), vec![8.0, 9.0]);
+    }
+
+    #[test]
+    fn test_opens() {
+        assert_eq!(opens(&candles()), vec![10.0, 11.0]);
+    }
+
+    #[test]
+    fn test_close_low_highs() {
+        assert_eq!(close_low_highs(&candles()), vec![(11.0, 8.0, 17.0), (12.0, 9.0, 18.0)]);
+    }
+
+    #[test]
+    fn test_medians() {
+        assert_eq!(medians(&candles()), vec![12.5, 13.5]);
+    }
+}
@@ -0,0 +1,93 @@
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum FractalKind {
+    /// Bearish fractal: a high with two lower highs on each side.
+    Up,
+    /// Bullish fractal: a low with two higher lows on each side.
+    Down,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Fractal {
+    pub index: usize,
+    pub kind: FractalKind,
+}
+
+/// Find the Bill Williams fractals in a `Vec<(f32, f32, f32)>` of price data.
+///
+/// ### Definition
+/// A fractal is a five-bar swing point. An up fractal is a bar whose high
+/// is greater than the highs of the two bars on either side of it, and a
+/// down fractal is a bar whose low is less than the lows of the two bars on
+/// either side of it. A single bar can be both.
+///
+/// NOTE: A fractal can only be confirmed two bars after it forms, so the
+/// first and last two bars of the series can never be fractals.
+///
+/// ### Usage
+/// Up fractals mark swing highs (resistance, bearish) and down fractals mark
+/// swing lows (support, bullish). They are used together with the Alligator
+/// to take breakouts beyond the last fractal, and as stop placement levels.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+///
+/// ### Example
+/// ```
+/// fractals::run(prices);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/articles/trading/06/fractals.asp
+pub fn run(prices: Vec<(f32, f32, f32)>) -> Vec<Fractal> {
+    let mut fractals: Vec<Fractal> = Vec::new();
+    if prices.len() < 5 { return fractals; }
+    for i in 2..prices.len()-2 {
+        let (_, low, high) = prices[i];
+        let neighbours = [prices[i-2], prices[i-1], prices[i+1], prices[i+2]];
+        if neighbours.iter().all(|p| p.2 < high) {
+            fractals.push(Fractal { index: i, kind: FractalKind::Up });
+        }
+        if neighbours.iter().all(|p| p.1 > low) {
+            fractals.push(Fractal { index: i, kind: FractalKind::Down });
+        }
+    }
+    return fractals;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_complex() {
+        let prices = vec![
+            (10.0, 9.0, 11.0), (11.0, 10.0, 12.0),
+            (13.0, 12.0, 15.0), (12.0, 11.0, 13.0),
+            (11.0, 10.0, 12.0), (9.0, 8.0, 11.0),
+            (7.0, 5.0, 10.0), (9.0, 8.0, 11.5),
+            (10.0, 9.0, 12.5), (11.0, 10.0, 13.0),
+        ];
+        assert_eq!(run(prices), vec![
+            Fractal { index: 2, kind: FractalKind::Up },
+            Fractal { index: 6, kind: FractalKind::Down },
+        ]);
+    }
+
+    #[test]
+    fn test_run_ties_are_not_fractals() {
+        let prices = vec![
+            (10.0, 9.0, 11.0), (11.0, 10.0, 15.0),
+            (13.0, 12.0, 15.0), (12.0, 11.0, 13.0),
+            (11.0, 10.0, 12.0),
+        ];
+        assert_eq!(run(prices), vec![]);
+    }
+
+    #[test]
+    fn test_run_not_enough_elements() {
+        assert_eq!(run(vec![(10.0, 9.0, 11.0), (13.0, 12.0, 15.0)]), vec![]);
+    }
+}
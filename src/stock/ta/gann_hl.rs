@@ -0,0 +1,108 @@
+use crate::stock::ta::sma;
+
+/// Calculate the Gann High-Low Activator based on a `Vec<(f32, f32, f32)>` of
+/// price data.
+///
+/// ### Definition
+/// A trend-following indicator that trails price with a moving average of
+/// either the highs or the lows, depending on which side of the market
+/// price is currently on. The line flips sides whenever price closes
+/// through the opposite average.
+///
+/// ### Formula
+/// `hi = sma(highs, n)`
+///
+/// `lo = sma(lows, n)`
+///
+/// `activator` conditions:
+/// - `lo` if c > hi (uptrend)
+/// - `hi` if c < lo (downtrend)
+/// - previous side if lo <= c <= hi
+///
+/// - `c`: current close price
+/// - `n`: number of periods to average
+///
+/// NOTE: The first reading has no previous side to fall back on, so when
+/// the close sits between both averages the side is chosen by comparing
+/// the close to the midpoint of `hi` and `lo`.
+///
+/// ### Usage
+/// While price stays above the activator the trend is considered bullish
+/// and the line acts as a trailing stop below price. A close below the low
+/// average flips the activator above price, signaling a bearish reversal.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of periods to average
+///
+/// ### Example
+/// ```
+/// gann_hl::run(prices, 3);
+/// gann_hl::run(prices, 10);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/articles/technical/03/032603.asp
+pub fn run(prices: Vec<(f32, f32, f32)>, period: usize) -> Vec<f32> {
+    if period == 0 || prices.len() < period { panic!("Not enough entries to calculate the Gann HiLo activator. Received {}, but required {}.", prices.len(), period); }
+    let lows: Vec<f32> = prices.iter().map(|p| p.1).collect();
+    let highs: Vec<f32> = prices.iter().map(|p| p.2).collect();
+    let lo_smas = sma::run(lows, period);
+    let hi_smas = sma::run(highs, period);
+
+    let mut activators: Vec<f32> = Vec::new();
+    let mut uptrend = false;
+    for i in 0..hi_smas.len() {
+        let close = prices[i+period-1].0;
+        let hi = hi_smas[i];
+        let lo = lo_smas[i];
+        if close > hi {
+            uptrend = true;
+        } else if close < lo {
+            uptrend = false;
+        } else if i == 0 {
+            uptrend = close >= (hi + lo) / 2.0;
+        }
+        activators.push(if uptrend { lo } else { hi });
+    }
+    return activators;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        let prices = vec![(10.0, 9.0, 11.0), (12.0, 11.0, 13.0), (14.0, 13.0, 15.0)];
+        assert_eq!(run(prices, 3), vec![11.0]);
+    }
+
+    #[test]
+    fn test_run_trend_reversal() {
+        let prices = [
+            (10.0, 9.0, 11.0), (12.0, 11.0, 13.0),
+            (14.0, 13.0, 15.0), (16.0, 15.0, 17.0),
+            (10.0, 9.0, 11.0), (6.0, 5.0, 7.0),
+        ];
+        let activators = run(prices.to_vec(), 3);
+        assert_eq!(activators, vec![11.0, 13.0, 14.333333, 11.666667]);
+
+        // Activator trails below price in the uptrend, then flips above it
+        assert_eq!(activators[1] < prices[3].0, true);
+        assert_eq!(activators[2] > prices[4].0, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the Gann HiLo activator. Received 1, but required 3.")]
+    fn test_run_not_enough_elements() {
+        run(vec![(10.0, 10.0, 10.0)], 3);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the Gann HiLo activator. Received 1, but required 0.")]
+    fn test_run_zero_period() {
+        run(vec![(10.0, 10.0, 10.0)], 0);
+    }
+}
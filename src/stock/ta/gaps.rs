@@ -0,0 +1,82 @@
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum GapKind {
+    /// Opened above the previous bar's high.
+    Up,
+    /// Opened below the previous bar's low.
+    Down,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Gap {
+    /// Index of the bar that gapped.
+    pub index: usize,
+    pub kind: GapKind,
+    /// Distance between the open and the previous bar's high (or low).
+    pub size: f32,
+}
+
+/// Find the opening gaps in a `&[(f32, f32, f32, f32)]` of price data.
+///
+/// A gap up is a bar opening above the previous bar's high, and a gap down
+/// a bar opening below the previous bar's low, so no trading took place in
+/// between. The `size` is always positive.
+///
+/// ### Usage
+/// Gaps mark news-driven moves. A gap that is not filled (price not
+/// trading back into it) shows strength in the gap's direction, and unfilled
+/// gaps often act as support/resistance later on.
+///
+/// # Arguments
+/// * `ohlc` - `&[(f32, f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(open, high, low, close)]`
+///
+/// ### Example
+/// ```
+/// ta::gaps(&ohlc);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/g/gap.asp
+pub fn gaps(ohlc: &[(f32, f32, f32, f32)]) -> Vec<Gap> {
+    let mut gaps: Vec<Gap> = Vec::new();
+    for i in 1..ohlc.len() {
+        let (_, prev_high, prev_low, _) = ohlc[i-1];
+        let (open, _, _, _) = ohlc[i];
+        if open > prev_high {
+            gaps.push(Gap { index: i, kind: GapKind::Up, size: open - prev_high });
+        } else if open < prev_low {
+            gaps.push(Gap { index: i, kind: GapKind::Down, size: prev_low - open });
+        }
+    }
+    return gaps;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gaps() {
+        let ohlc = vec![
+            (10.0, 11.0, 9.5, 10.5),
+            (10.5, 11.5, 10.0, 11.0),
+            (12.5, 13.0, 12.0, 12.5),
+            (12.5, 13.0, 12.0, 12.2),
+            (11.0, 11.5, 10.5, 11.0),
+            (11.0, 11.5, 10.5, 11.2),
+        ];
+        assert_eq!(gaps(&ohlc), vec![
+            Gap { index: 2, kind: GapKind::Up, size: 1.0 },
+            Gap { index: 4, kind: GapKind::Down, size: 1.0 },
+        ]);
+    }
+
+    #[test]
+    fn test_gaps_open_at_previous_high() {
+        let ohlc = vec![(10.0, 11.0, 9.5, 10.5), (11.0, 11.5, 10.0, 11.0)];
+        assert_eq!(gaps(&ohlc), Vec::new());
+        assert_eq!(gaps(&[]), Vec::new());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::stock::ta::ema_ribbon;
+
+const SHORT: [usize; 6] = [3, 5, 8, 10, 12, 15];
+const LONG: [usize; 6] = [30, 35, 40, 45, 50, 60];
+
+/// Calculate the Guppy Multiple Moving Average (GMMA) of a `Vec<f32>` of
+/// price data.
+///
+/// ### Definition
+/// Two groups of EMAs: a short-term group (3, 5, 8, 10, 12 and 15 periods)
+/// tracking traders, and a long-term group (30, 35, 40, 45, 50 and 60
+/// periods) tracking investors.
+///
+/// ### Usage
+/// Wide separation within and between the groups signals a strong trend.
+/// The short-term group compressing and crossing through the long-term
+/// group signals a possible reversal, while the long-term group staying
+/// wide shows investors still support the trend.
+///
+/// ### Alignment
+/// Every line of both groups is aligned to the 60-period EMA, so index `k`
+/// of every line corresponds to `prices[60 + k]` (see `ta::ema_ribbon`).
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+///
+/// # Returns
+/// `(Vec<Vec<f32>>, Vec<Vec<f32>>)` in the format of `(short-term group, long-term group)`,
+/// with the lines of each group ordered from the shortest period to the longest.
+///
+/// ### Example
+/// ```
+/// gmma::run(prices);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/g/guppy-multiple-moving-average.asp
+pub fn run(prices: Vec<f32>) -> (Vec<Vec<f32>>, Vec<Vec<f32>>) {
+    let periods: Vec<usize> = SHORT.iter().chain(LONG.iter()).copied().collect();
+    let mut lines = ema_ribbon(&prices, &periods);
+    let long = lines.split_off(SHORT.len());
+    return (lines, long);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::ta::ema;
+
+    #[test]
+    fn test_run_groups() {
+        let prices: Vec<f32> = (0..80).map(|i| 100.0 + i as f32).collect();
+        let (short, long) = run(prices.to_vec());
+        assert_eq!(short.len(), 6);
+        assert_eq!(long.len(), 6);
+        for line in short.iter().chain(long.iter()) {
+            assert_eq!(line.len(), 20);
+        }
+        assert_eq!(long[5], ema::run(prices.to_vec(), 60));
+        // In an uptrend the short-term group sits above the long-term group
+        assert_eq!(short[5][0] > long[0][0], true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the EMA ribbon. Received 10, but required 61 (periods+1).")]
+    fn test_run_not_enough_elements() {
+        run(vec![100.0; 10]);
+    }
+}
@@ -0,0 +1,138 @@
+/// Number of bars the estimator needs before its output is emitted.
+pub const WARMUP: usize = 32;
+
+/// Estimate the dominant cycle period of a `Vec<f32>` of price data using
+/// Ehlers' Hilbert transform.
+///
+/// ### Definition
+/// Splits the (smoothed, detrended) price into an in-phase component and a
+/// quadrature component, 90 degrees out of phase with it, using a Hilbert
+/// transform approximated by a 7-tap FIR filter. The phase of the resulting
+/// analytic signal advances by `360 / period` degrees per bar, so the change
+/// in phase between bars measures the period of the dominant cycle.
+///
+/// ### Formula
+/// `smooth = (4p + 3p_1 + 2p_2 + p_3) / 10`
+///
+/// `ht(x) = (0.0962x + 0.5769x_2 - 0.5769x_4 - 0.0962x_6) * (0.075period_1 + 0.54)`
+///
+/// `detrender = ht(smooth)`, `q1 = ht(detrender)`, `i1 = detrender_3`
+///
+/// `i2 = i1 - ht(q1)`, `q2 = q1 + ht(i1)` (each smoothed by `0.2x + 0.8x_1`)
+///
+/// `re = i2 * i2_1 + q2 * q2_1`, `im = i2 * q2_1 - q2 * i2_1` (each smoothed by `0.2x + 0.8x_1`)
+///
+/// `period = 360 / atan(im / re)` (in degrees), limited to 0.67-1.5 times the
+/// previous period and to [6, 50], then smoothed by `0.2x + 0.8x_1`
+///
+/// `dominant_cycle = 0.33period + 0.67dominant_cycle_1`
+///
+/// - `x_n`: value `n` bars ago
+///
+/// ### Warm-up
+/// The filters chain over 30+ bars of history, so the first `WARMUP` (32)
+/// bars are calculated but not emitted: the first value corresponds to
+/// `prices[32]`. Even after that, the estimate starts from the default of 6
+/// bars and needs a few cycles of data to converge on the actual period.
+///
+/// ### Usage
+/// Feeds adaptive indicators (e.g. `rsi::run_adaptive`) a period that
+/// follows the market's current rhythm rather than a fixed one.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+///
+/// ### Example
+/// ```
+/// ht_period::dominant_cycle(prices);
+/// ```
+///
+/// #### Resources
+/// - John F. Ehlers, "Rocket Science for Traders" (2001), chapters 6-7
+pub fn dominant_cycle(prices: Vec<f32>) -> Vec<f32> {
+    if prices.len() < WARMUP+1 { panic!("Not enough entries to calculate the dominant cycle period. Received {}, but required {}.", prices.len(), WARMUP+1); }
+    let n = prices.len();
+    let p: Vec<f64> = prices.iter().map(|&v| v as f64).collect();
+    let mut smooth = vec![0.0; n];
+    let mut detrender = vec![0.0; n];
+    let mut q1 = vec![0.0; n];
+    let mut i1 = vec![0.0; n];
+    let mut i2_prev = 0.0;
+    let mut q2_prev = 0.0;
+    let mut re_prev = 0.0;
+    let mut im_prev = 0.0;
+    let mut period_prev: f64 = 6.0;
+    let mut cycle_prev: f64 = 6.0;
+    let mut cycles: Vec<f32> = Vec::new();
+
+    // Hilbert transform FIR over the 7 latest values of `x`
+    let ht = |x: &Vec<f64>, i: usize, adj: f64| -> f64 {
+        if i < 6 { return 0.0; }
+        return (0.0962 * x[i] + 0.5769 * x[i-2] - 0.5769 * x[i-4] - 0.0962 * x[i-6]) * adj;
+    };
+
+    for i in 0..n {
+        if i >= 3 { smooth[i] = (4.0 * p[i] + 3.0 * p[i-1] + 2.0 * p[i-2] + p[i-3]) / 10.0; }
+        let adj = 0.075 * period_prev + 0.54;
+        detrender[i] = ht(&smooth, i, adj);
+        q1[i] = ht(&detrender, i, adj);
+        i1[i] = if i >= 3 { detrender[i-3] } else { 0.0 };
+
+        // Advance the phase of i1 and q1 by 90 degrees
+        let ji = ht(&i1, i, adj);
+        let jq = ht(&q1, i, adj);
+        let i2 = 0.2 * (i1[i] - jq) + 0.8 * i2_prev;
+        let q2 = 0.2 * (q1[i] + ji) + 0.8 * q2_prev;
+
+        // Homodyne discriminator
+        let re = 0.2 * (i2 * i2_prev + q2 * q2_prev) + 0.8 * re_prev;
+        let im = 0.2 * (i2 * q2_prev - q2 * i2_prev) + 0.8 * im_prev;
+        i2_prev = i2;
+        q2_prev = q2;
+        re_prev = re;
+        im_prev = im;
+
+        let mut period = period_prev;
+        if im != 0.0 && re != 0.0 { period = 360.0 / (im / re).atan().to_degrees(); }
+        period = period.min(1.5 * period_prev).max(0.67 * period_prev).clamp(6.0, 50.0);
+        period = 0.2 * period + 0.8 * period_prev;
+        period_prev = period;
+
+        let cycle = 0.33 * period + 0.67 * cycle_prev;
+        cycle_prev = cycle;
+        if i >= WARMUP { cycles.push(cycle as f32); }
+    }
+    return cycles;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sinusoid(period: f32, n: usize) -> Vec<f32> {
+        return (0..n).map(|i| 100.0 + 10.0 * (2.0 * std::f32::consts::PI * i as f32 / period).sin()).collect();
+    }
+
+    #[test]
+    fn test_dominant_cycle_sinusoid() {
+        for &period in [15.0, 20.0, 30.0].iter() {
+            let cycles = dominant_cycle(sinusoid(period, 500));
+            assert_eq!(cycles.len(), 500 - WARMUP);
+            let last = cycles[cycles.len()-1];
+            assert_eq!((last - period).abs() < period * 0.1, true);
+        }
+    }
+
+    #[test]
+    fn test_dominant_cycle_bounds() {
+        let prices: Vec<f32> = (0..200).map(|i| 100.0 + i as f32).collect();
+        let cycles = dominant_cycle(prices);
+        assert_eq!(cycles.iter().all(|&c| (6.0..=50.0).contains(&c)), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the dominant cycle period. Received 10, but required 33.")]
+    fn test_dominant_cycle_not_enough_elements() {
+        dominant_cycle(vec![100.0; 10]);
+    }
+}
@@ -0,0 +1,102 @@
+use crate::stock::ta::{ema, macd};
+
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Impulse {
+    /// EMA and MACD histogram are both rising.
+    Green,
+    /// EMA and MACD histogram are both falling.
+    Red,
+    /// EMA and MACD histogram disagree.
+    Blue,
+}
+
+/// Calculate the Elder Impulse System based on a `Vec<f32>` of price data.
+///
+/// ### Definition
+/// Combines a trend-following indicator (EMA) with a momentum indicator
+/// (MACD histogram) into a discrete signal for each bar.
+///
+/// ### Formula
+/// `impulse` conditions:
+/// - `Green` if ema > ema_prev and hist > hist_prev
+/// - `Red` if ema < ema_prev and hist < hist_prev
+/// - `Blue` otherwise
+///
+/// - `ema`: EMA of the closing price
+/// - `hist`: MACD histogram (MACD - Signal)
+///
+/// NOTE: Both the EMA and MACD histogram must have a previous reading, so the
+/// first impulse corresponds to `prices[max(ema_period, slow + signal) + 1]`.
+///
+/// ### Usage
+/// Green bars show bulls are in control and shorting should be avoided. Red
+/// bars show bears are in control and buying should be avoided. Blue bars
+/// show neither side is in control, allowing either position.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `ema_period` - Number of periods for the trend EMA (commonly 13)
+/// * `macd` - Periods for the MACD in the format of `(fast, slow, signal)`
+///
+/// ### Example
+/// ```
+/// impulse::run(prices, 13, (12, 26, 9));
+/// ```
+///
+/// #### Resources
+/// - https://school.stockcharts.com/doku.php?id=chart_analysis:elder_impulse_system
+pub fn run(prices: Vec<f32>, ema_period: usize, macd: (usize, usize, usize)) -> Vec<Impulse> {
+    let (fast, slow, signal) = macd;
+    let start = std::cmp::max(ema_period, slow + signal) + 1;
+    if prices.len() < start+1 { panic!("Not enough entries to calculate the impulse system. Received {}, but required {}.", prices.len(), start+1); }
+
+    let emas = ema::run(prices.to_vec(), ema_period);
+    let (macds, signals) = macd::run_with(prices.to_vec(), fast, slow, signal);
+    let hist = macd::histogram(&macds, &signals);
+
+    let mut impulses: Vec<Impulse> = Vec::new();
+    for i in start..prices.len() {
+        // `emas[0]` corresponds to `prices[ema_period]` and `hist[0]` to `prices[slow + signal]`
+        let e = i - ema_period;
+        let h = i - slow - signal;
+        let ema_rising = emas[e] > emas[e-1];
+        let ema_falling = emas[e] < emas[e-1];
+        let hist_rising = hist[h] > hist[h-1];
+        let hist_falling = hist[h] < hist[h-1];
+        if ema_rising && hist_rising {
+            impulses.push(Impulse::Green);
+        } else if ema_falling && hist_falling {
+            impulses.push(Impulse::Red);
+        } else {
+            impulses.push(Impulse::Blue);
+        }
+    }
+    return impulses;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_uptrend() {
+        let prices: Vec<f32> = (0..24).map(|i| 10.0 * 1.05_f32.powi(i)).collect();
+        let impulses = run(prices, 5, (3, 6, 3));
+        assert_eq!(impulses.len(), 14);
+        assert_eq!(impulses.iter().all(|i| *i == Impulse::Green), true);
+    }
+
+    #[test]
+    fn test_run_downtrend() {
+        let prices: Vec<f32> = (0..24).map(|i| 100.0 * 0.95_f32.powi(i)).collect();
+        let impulses = run(prices, 5, (3, 6, 3));
+        assert_eq!(impulses.iter().all(|i| *i == Impulse::Red), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the impulse system. Received 5, but required 11.")]
+    fn test_run_not_enough_elements() {
+        run(vec![10.0, 10.0, 15.0, 20.0, 20.0], 5, (3, 6, 3));
+    }
+}
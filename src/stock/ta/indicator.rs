@@ -0,0 +1,270 @@
+use crate::stock::ta::{atr, cci, ema, macd, rsi, sma, stochastic_oscillator, williams_r};
+
+/// Common interface for technical indicators so they can be stored and
+/// dispatched uniformly (e.g. as a `Vec<Box<dyn Indicator<Input = Vec<f32>, Output = Vec<f32>>>>`).
+///
+/// ### Example
+/// ```
+/// let indicators: Vec<Box<dyn Indicator<Input = Vec<f32>, Output = Vec<f32>>>> = vec![Box::new(rsi::Rsi { period: 14 })];
+/// for indicator in indicators.iter() {
+///     indicator.compute(&prices);
+/// }
+/// ```
+pub trait Indicator {
+    type Input;
+    type Output;
+
+    /// Run the indicator over `data`.
+    fn compute(&self, data: &Self::Input) -> Self::Output;
+
+    /// Number of input entries consumed before the first output value, i.e.
+    /// the index in the input that the first output value corresponds to.
+    fn warmup(&self) -> usize;
+
+    /// Whether the indicator needs volume data, so an engine can skip it
+    /// when only prices are available.
+    fn requires_volume(&self) -> bool { return false; }
+}
+
+/// Stable identity of an indicator and its configuration, so a config can be
+/// stored (e.g. in a database) and re-run later.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum IndicatorId {
+    Rsi { period: usize },
+    Stochastic { period: usize },
+    WilliamsR { period: usize },
+    Cci { period: usize },
+    Atr { period: usize },
+    Sma { period: usize },
+    Ema { period: usize },
+    /// Runs to the MACD histogram, so every period affects the output.
+    Macd { fast: usize, slow: usize, signal: usize },
+}
+
+/// Version of the `IndicatorId` JSON schema, bumped whenever the schema
+/// changes incompatibly.
+#[cfg(feature = "serde")]
+pub const INDICATOR_ID_VERSION: u64 = 1;
+
+impl IndicatorId {
+    /// Run the identified indicator. Indicators of a single price use the
+    /// closes.
+    ///
+    /// # Arguments
+    /// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+    ///   in the format of `&[(close, low, high)]`
+    ///
+    /// ### Example
+    /// ```
+    /// IndicatorId::Rsi { period: 14 }.run(&prices);
+    /// ```
+    pub fn run(&self, prices: &[(f32, f32, f32)]) -> Vec<f32> {
+        let closes = || prices.iter().map(|p| p.0).collect::<Vec<f32>>();
+        match *self {
+            IndicatorId::Rsi { period } => return rsi::run(closes(), period),
+            IndicatorId::Stochastic { period } => return stochastic_oscillator::run(prices.to_vec(), period),
+            IndicatorId::WilliamsR { period } => return williams_r::run(prices.to_vec(), period),
+            IndicatorId::Cci { period } => return cci::run(prices.to_vec(), period),
+            IndicatorId::Atr { period } => return atr::run(prices.to_vec(), period),
+            IndicatorId::Sma { period } => return sma::run(closes(), period),
+            IndicatorId::Ema { period } => return ema::run(closes(), period),
+            IndicatorId::Macd { fast, slow, signal } => {
+                let (macds, signals) = macd::run_with(closes(), fast, slow, signal);
+                return macd::histogram(&macds, &signals);
+            },
+        }
+    }
+
+    /// Name of the indicator in the JSON schema.
+    #[cfg(feature = "serde")]
+    fn kind(&self) -> &'static str {
+        match *self {
+            IndicatorId::Rsi { .. } => return "rsi",
+            IndicatorId::Stochastic { .. } => return "stochastic",
+            IndicatorId::WilliamsR { .. } => return "williams_r",
+            IndicatorId::Cci { .. } => return "cci",
+            IndicatorId::Atr { .. } => return "atr",
+            IndicatorId::Sma { .. } => return "sma",
+            IndicatorId::Ema { .. } => return "ema",
+            IndicatorId::Macd { .. } => return "macd",
+        }
+    }
+
+    /// Serialize the `IndicatorId` to JSON.
+    ///
+    /// ### Schema
+    /// ```json
+    /// { "version": 1, "kind": "rsi", "period": 14 }
+    /// { "version": 1, "kind": "macd", "fast": 12, "slow": 26, "signal": 9 }
+    /// ```
+    ///
+    /// ### Example
+    /// ```
+    /// IndicatorId::Rsi { period: 14 }.to_json();
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json(&self) -> String {
+        let params = match *self {
+            IndicatorId::Macd { fast, slow, signal } => format!("\"fast\": {},\"slow\": {},\"signal\": {}", fast, slow, signal),
+            IndicatorId::Rsi { period } | IndicatorId::Stochastic { period } | IndicatorId::WilliamsR { period }
+            | IndicatorId::Cci { period } | IndicatorId::Atr { period } | IndicatorId::Sma { period }
+            | IndicatorId::Ema { period } => format!("\"period\": {}", period),
+        };
+        return format!("{{\"version\": {},\"kind\": \"{}\",{}}}", INDICATOR_ID_VERSION, self.kind(), params);
+    }
+
+    /// Parse an `IndicatorId` from the JSON emitted by `to_json`.
+    ///
+    /// ### Example
+    /// ```
+    /// IndicatorId::from_json("{\"version\": 1, \"kind\": \"rsi\", \"period\": 14}");
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_json(json: &str) -> Result<IndicatorId, String> {
+        let root: serde_json::Value = match serde_json::from_str(json) {
+            Ok(v) => v,
+            Err(e) => return Err(format!("Could not parse the indicator. {}", e)),
+        };
+        match root["version"].as_u64() {
+            Some(INDICATOR_ID_VERSION) => {},
+            Some(v) => return Err(format!("Could not parse the indicator. Unsupported version {}.", v)),
+            None => return Err("Could not parse the indicator. Missing `version`.".to_owned()),
+        };
+        let param = |name: &str| -> Result<usize, String> {
+            match root[name].as_u64() {
+                Some(v) => return Ok(v as usize),
+                None => return Err(format!("Could not parse the indicator. Missing `{}`.", name)),
+            }
+        };
+        match root["kind"].as_str() {
+            Some("rsi") => return Ok(IndicatorId::Rsi { period: param("period")? }),
+            Some("stochastic") => return Ok(IndicatorId::Stochastic { period: param("period")? }),
+            Some("williams_r") => return Ok(IndicatorId::WilliamsR { period: param("period")? }),
+            Some("cci") => return Ok(IndicatorId::Cci { period: param("period")? }),
+            Some("atr") => return Ok(IndicatorId::Atr { period: param("period")? }),
+            Some("sma") => return Ok(IndicatorId::Sma { period: param("period")? }),
+            Some("ema") => return Ok(IndicatorId::Ema { period: param("period")? }),
+            Some("macd") => return Ok(IndicatorId::Macd { fast: param("fast")?, slow: param("slow")?, signal: param("signal")? }),
+            Some(kind) => return Err(format!("Could not parse the indicator. Unknown kind `{}`.", kind)),
+            None => return Err("Could not parse the indicator. Missing `kind`.".to_owned()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::ta::{mfi, obv, vwap};
+
+    /// Boxed indicator over `(close, low, high)` bars.
+    type BarIndicator = Box<dyn Indicator<Input = Vec<(f32, f32, f32)>, Output = Vec<f32>>>;
+    /// Boxed indicator over `(close, low, high)` bars and their volumes.
+    type VolumeIndicator = Box<dyn Indicator<Input = (Vec<(f32, f32, f32)>, Vec<u64>), Output = Vec<f32>>>;
+
+    #[test]
+    fn test_dispatch_rsi() {
+        let prices = vec![10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0];
+        let indicators: Vec<Box<dyn Indicator<Input = Vec<f32>, Output = Vec<f32>>>> = vec![Box::new(rsi::Rsi { period: 14 })];
+        for indicator in indicators.iter() {
+            assert_eq!(indicator.compute(&prices), rsi::run(prices.to_vec(), 14));
+            assert_eq!(indicator.warmup(), 14);
+            assert_eq!(indicator.warmup() + indicator.compute(&prices).len(), prices.len());
+        }
+    }
+
+    #[test]
+    fn test_dispatch_stochastic_oscillator() {
+        let prices = vec![
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+        ];
+        let indicators: Vec<BarIndicator> = vec![Box::new(stochastic_oscillator::StochasticOscillator { period: 14 })];
+        for indicator in indicators.iter() {
+            assert_eq!(indicator.compute(&prices), stochastic_oscillator::run(prices.to_vec(), 14));
+            assert_eq!(indicator.warmup(), 13);
+            assert_eq!(indicator.warmup() + indicator.compute(&prices).len(), prices.len());
+        }
+    }
+
+    #[test]
+    fn test_requires_volume() {
+        assert_eq!(rsi::Rsi { period: 14 }.requires_volume(), false);
+        assert_eq!(stochastic_oscillator::StochasticOscillator { period: 14 }.requires_volume(), false);
+        assert_eq!(obv::Obv {}.requires_volume(), true);
+        assert_eq!(vwap::Vwap {}.requires_volume(), true);
+        assert_eq!(mfi::Mfi { period: 14 }.requires_volume(), true);
+    }
+
+    #[test]
+    fn test_dispatch_volume_indicators() {
+        let prices = vec![(10.0, 9.0, 11.0), (12.0, 11.0, 13.0), (11.0, 10.0, 12.0), (13.0, 12.0, 14.0)];
+        let volumes: Vec<u64> = vec![100, 100, 100, 200];
+        let indicators: Vec<VolumeIndicator> = vec![
+            Box::new(vwap::Vwap {}),
+            Box::new(mfi::Mfi { period: 3 }),
+        ];
+        let data = (prices, volumes);
+        for indicator in indicators.iter() {
+            assert_eq!(indicator.requires_volume(), true);
+            assert_eq!(indicator.warmup() + indicator.compute(&data).len(), data.0.len());
+        }
+    }
+
+    fn bars() -> Vec<(f32, f32, f32)> {
+        return (0..40).map(|i| {
+            let c = 100.0 + (i as f32 * 0.7).sin() * 5.0 + i as f32 * 0.2;
+            return (c, c - 1.0, c + 1.5);
+        }).collect();
+    }
+
+    #[test]
+    fn test_indicator_id_run() {
+        let prices = bars();
+        let closes: Vec<f32> = prices.iter().map(|p| p.0).collect();
+        assert_eq!(IndicatorId::Rsi { period: 14 }.run(&prices), rsi::run(closes.to_vec(), 14));
+        assert_eq!(IndicatorId::Stochastic { period: 14 }.run(&prices), stochastic_oscillator::run(prices.to_vec(), 14));
+        assert_eq!(IndicatorId::Ema { period: 10 }.run(&prices), ema::run(closes.to_vec(), 10));
+        let (macds, signals) = macd::run_with(closes.to_vec(), 5, 10, 4);
+        assert_eq!(IndicatorId::Macd { fast: 5, slow: 10, signal: 4 }.run(&prices), macd::histogram(&macds, &signals));
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_indicator_id_round_trip() {
+        let prices = bars();
+        let ids = [
+            IndicatorId::Rsi { period: 14 },
+            IndicatorId::Stochastic { period: 5 },
+            IndicatorId::WilliamsR { period: 14 },
+            IndicatorId::Cci { period: 20 },
+            IndicatorId::Atr { period: 14 },
+            IndicatorId::Sma { period: 20 },
+            IndicatorId::Ema { period: 10 },
+            IndicatorId::Macd { fast: 12, slow: 26, signal: 9 },
+        ];
+        for id in ids.iter() {
+            let parsed = match IndicatorId::from_json(&id.to_json()) {
+                Ok(v) => v,
+                Err(e) => panic!("{}", e),
+            };
+            assert_eq!(parsed, *id);
+            assert_eq!(parsed.run(&prices), id.run(&prices));
+        }
+        assert_eq!(IndicatorId::Rsi { period: 14 }.to_json(), "{\"version\": 1,\"kind\": \"rsi\",\"period\": 14}");
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_indicator_id_from_json_invalid() {
+        assert_eq!(IndicatorId::from_json("{\"version\": 2, \"kind\": \"rsi\", \"period\": 14}"), Err("Could not parse the indicator. Unsupported version 2.".to_owned()));
+        assert_eq!(IndicatorId::from_json("{\"version\": 1, \"kind\": \"adx\", \"period\": 14}"), Err("Could not parse the indicator. Unknown kind `adx`.".to_owned()));
+        assert_eq!(IndicatorId::from_json("{\"version\": 1, \"kind\": \"macd\", \"fast\": 12}"), Err("Could not parse the indicator. Missing `slow`.".to_owned()));
+    }
+}
@@ -0,0 +1,117 @@
+/// Least-squares line fitted to a window of prices.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub struct Regression {
+    /// Change in price per bar.
+    pub slope: f32,
+    /// Fitted price of the first bar in the window.
+    pub intercept: f32,
+    /// Goodness of fit between 0 (no linear relationship) and 1 (all prices on the line).
+    pub r_squared: f32,
+}
+
+impl Regression {
+    /// Fitted price of the last bar in a window of `period` prices, i.e. the
+    /// linear regression (time series forecast) value.
+    pub fn end_value(&self, period: usize) -> f32 {
+        return self.intercept + self.slope * (period as f32 - 1.0);
+    }
+}
+
+/// Fit a least-squares line through a `&[f32]` of prices against their index.
+///
+/// ### Formula
+/// `slope = sum((x - x_mean) * (y - y_mean)) / sum((x - x_mean)^2)`
+///
+/// `intercept = y_mean - (slope * x_mean)`
+///
+/// `r_squared = 1 - (ss_res / ss_tot)`
+///
+/// NOTE: A window where every price is equal has no variance to explain, so
+/// its `r_squared` is 0.
+///
+/// ### Example
+/// ```
+/// linreg::fit(&prices[prices.len()-20..]);
+/// ```
+pub fn fit(values: &[f32]) -> Regression {
+    if values.len() < 2 { panic!("Not enough entries to calculate the linear regression. Received {}, but required 2.", values.len()); }
+    let n = values.len() as f64;
+    let x_mean = (n - 1.0) / 2.0;
+    let y_mean = values.iter().map(|&v| v as f64).sum::<f64>() / n;
+    let mut sxy = 0.0;
+    let mut sxx = 0.0;
+    for (i, &v) in values.iter().enumerate() {
+        let dx = i as f64 - x_mean;
+        sxy += dx * (v as f64 - y_mean);
+        sxx += dx * dx;
+    }
+    let slope = sxy / sxx;
+    let intercept = y_mean - (slope * x_mean);
+    let mut ss_res = 0.0;
+    let mut ss_tot = 0.0;
+    for (i, &v) in values.iter().enumerate() {
+        let fitted = intercept + slope * i as f64;
+        ss_res += (v as f64 - fitted) * (v as f64 - fitted);
+        ss_tot += (v as f64 - y_mean) * (v as f64 - y_mean);
+    }
+    let r_squared = if ss_tot == 0.0 { 0.0 } else { (1.0 - (ss_res / ss_tot)).max(0.0) };
+    return Regression { slope: slope as f32, intercept: intercept as f32, r_squared: r_squared as f32 };
+}
+
+/// Calculate the rolling linear regression of a `Vec<f32>` of price data.
+///
+/// ### Usage
+/// The slope shows the direction and speed of the trend, while the
+/// R-squared shows how much of the price action the trend explains.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `period` - Number of prices in each regression window
+///
+/// # Returns
+/// `Vec<Regression>` where the first value corresponds to `prices[period - 1]`.
+///
+/// ### Example
+/// ```
+/// linreg::run(prices, 20);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/l/linearregressionindicator.asp
+pub fn run(prices: Vec<f32>, period: usize) -> Vec<Regression> {
+    if period < 2 || prices.len() < period { panic!("Not enough entries to calculate the linear regression. Received {}, but required {}.", prices.len(), period.max(2)); }
+    return prices.windows(period).map(fit).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fit_line() {
+        let r = fit(&[10.0, 12.0, 14.0, 16.0]);
+        assert_eq!(r, Regression { slope: 2.0, intercept: 10.0, r_squared: 1.0 });
+        assert_eq!(r.end_value(4), 16.0);
+    }
+
+    #[test]
+    fn test_fit_flat() {
+        assert_eq!(fit(&[10.0, 10.0, 10.0]), Regression { slope: 0.0, intercept: 10.0, r_squared: 0.0 });
+    }
+
+    #[test]
+    fn test_run_complex() {
+        let regressions = run(vec![10.0, 12.0, 11.0, 13.0], 3);
+        assert_eq!(regressions.len(), 2);
+        assert_eq!(regressions[0].slope, 0.5);
+        assert_eq!(regressions[1].slope, 0.5);
+        assert_eq!((regressions[0].r_squared - 0.25).abs() < 1e-6, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the linear regression. Received 2, but required 5.")]
+    fn test_run_not_enough_elements() {
+        run(vec![10.0, 12.0], 5);
+    }
+}
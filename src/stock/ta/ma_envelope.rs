@@ -0,0 +1,71 @@
+use crate::stock::ta::sma;
+
+/// Calculate moving average envelopes of a `Vec<f32>` of price data.
+///
+/// ### Definition
+/// A channel made of the SMA and two bands a fixed percentage above and
+/// below it.
+///
+/// ### Formula
+/// `middle = sma(prices, n)`
+///
+/// `upper = middle * (1 + (percent / 100))`
+///
+/// `lower = middle * (1 - (percent / 100))`
+///
+/// ### Usage
+/// In a range, price reaching the upper band is considered overbought and
+/// the lower band oversold. In a strong trend, price riding along a band
+/// shows the trend's strength instead. The percentage is tuned to the
+/// security's volatility so most price action stays inside the envelope.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `period` - Number of periods to average
+/// * `percent` - Distance of each band from the SMA in percent (e.g. `2.5`)
+///
+/// # Returns
+/// `Vec<(f32, f32, f32)>` in the format of `(lower, middle, upper)`. The first
+/// value corresponds to `prices[period - 1]`.
+///
+/// ### Example
+/// ```
+/// ma_envelope::run(prices, 20, 2.5);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/m/movingaverageenvelope.asp
+pub fn run(prices: Vec<f32>, period: usize, percent: f32) -> Vec<(f32, f32, f32)> {
+    let offset = percent / 100.0;
+    return sma::run(prices, period).iter().map(|&m| (m * (1.0 - offset), m, m * (1.0 + offset))).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        let prices = vec![10.0, 10.0, 15.0, 20.0, 20.0];
+        assert_eq!(run(prices, 5, 10.0), vec![(13.5, 15.0, 16.5)]);
+    }
+
+    #[test]
+    fn test_run_band_width_scales() {
+        let prices = [10.0, 10.0, 15.0, 20.0, 20.0, 10.0, 10.0, 10.0];
+        let narrow = run(prices.to_vec(), 5, 2.0);
+        let wide = run(prices.to_vec(), 5, 4.0);
+        for i in 0..narrow.len() {
+            assert_eq!(narrow[i].1, wide[i].1);
+            let narrow_width = narrow[i].2 - narrow[i].0;
+            let wide_width = wide[i].2 - wide[i].0;
+            assert_eq!((wide_width - 2.0 * narrow_width).abs() < 1e-4, true);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the SMA. Received 1, but required 5.")]
+    fn test_run_not_enough_elements() {
+        run(vec![10.0], 5, 2.5);
+    }
+}
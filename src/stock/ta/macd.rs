@@ -1,4 +1,6 @@
-use crate::stock::ta::ema;
+use std::collections::VecDeque;
+
+use crate::stock::ta::{ema, ema::EmaState, signal_line, Smoothing};
 
 /// Calculate the moving average convergence divergence (MACD) of a `Vec<f32>` of price data.
 /// 
@@ -43,32 +45,176 @@ use crate::stock::ta::ema;
 /// #### Resources
 /// - https://www.investopedia.com/terms/m/macd.asp
 pub fn run(prices: Vec<f32>) -> (Vec<f32>, Vec<f32>) {
-    const LOW_PERIOD: usize = 12;
-    const HIGH_PERIOD: usize = 26;
-    if prices.len() < HIGH_PERIOD+1 { panic!("Not enough entries to calculate the EMA. Received {}, but required {} (26+1).", prices.len(), HIGH_PERIOD+1); }
-    
+    return run_with(prices, 12, 26, 9);
+}
+
+/// Calculate the MACD of a `Vec<f32>` of price data using custom periods.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `fast` - Number of periods for the fast EMA (12 in `run`)
+/// * `slow` - Number of periods for the slow EMA (26 in `run`)
+/// * `signal` - Number of periods for the signal line EMA (9 in `run`)
+///
+/// # Returns
+/// `(Vec<f32>, Vec<f32>)` containing values in the form of `(MACD, Signal)`.
+/// The first MACD value corresponds to `prices[slow]`.
+///
+/// NOTE: Panics unless `0 < fast < slow` and `signal > 0`.
+///
+/// ### Example
+/// ```
+/// macd::run_with(prices, 12, 26, 9);
+/// macd::run_with(prices, 5, 35, 5);
+/// ```
+pub fn run_with(prices: Vec<f32>, fast: usize, slow: usize, signal: usize) -> (Vec<f32>, Vec<f32>) {
+    validate_periods(fast, slow, signal);
+    if prices.len() < slow+1 { panic!("Not enough entries to calculate the EMA. Received {}, but required {} ({}+1).", prices.len(), slow+1, slow); }
+
     let mut macd: Vec<f32> = Vec::new();
     // EMA calculation requires `period+1` entries
-    let mut i = HIGH_PERIOD+1;
+    let mut i = slow+1;
     while {
-        let emas12 = ema::run(prices[i-1-HIGH_PERIOD..i].to_vec(), LOW_PERIOD);
-        let emas26 = ema::run(prices[i-1-HIGH_PERIOD..i].to_vec(), HIGH_PERIOD);
-        macd.push(emas12[emas12.len()-1] - emas26[emas26.len()-1]);
+        let emas_fast = ema::run(prices[i-1-slow..i].to_vec(), fast);
+        let emas_slow = ema::run(prices[i-1-slow..i].to_vec(), slow);
+        macd.push(emas_fast[emas_fast.len()-1] - emas_slow[emas_slow.len()-1]);
         i += 1;
         i <= prices.len()
     } {}
 
     // Calculate the MACD signal line
-    let mut signal: Vec<f32> = Vec::new();
-    if macd.len() > 9 { signal = ema::run(macd.to_vec(), 9); };
+    let signal = signal_line(&macd, signal, Smoothing::Ema);
 
     return (macd, signal);
 }
 
+/// Panic on periods shared by `run_with` and `MacdState`.
+fn validate_periods(fast: usize, slow: usize, signal: usize) {
+    if fast == 0 || fast >= slow { panic!("Invalid periods for MACD. Received fast {} and slow {}, but required 0 < fast < slow.", fast, slow); }
+    if signal == 0 { panic!("Invalid signal period for MACD. Received 0, but required at least 1."); }
+}
+
+/// Incremental MACD for live data, fed one price at a time and giving the
+/// same values as `run_with`.
+///
+/// Like `run_with`, each MACD value is calculated from EMAs seeded over the
+/// latest `slow+1` prices, so only that window is kept and an update is
+/// O(slow) regardless of how much history has been fed. The signal line is
+/// a running EMA of the MACD in O(1).
+///
+/// ### Example
+/// ```
+/// let mut state = macd::MacdState::new(12, 26, 9);
+/// for price in prices {
+///     if let Some((macd, signal, histogram)) = state.update(price) { ... }
+/// }
+/// ```
+pub struct MacdState {
+    fast: usize,
+    slow: usize,
+    window: VecDeque<f32>,
+    signal: EmaState,
+}
+
+impl MacdState {
+    pub fn new(fast: usize, slow: usize, signal: usize) -> Self {
+        validate_periods(fast, slow, signal);
+        return Self {
+            fast: fast,
+            slow: slow,
+            window: VecDeque::with_capacity(slow + 2),
+            signal: EmaState::new(signal),
+        };
+    }
+
+    /// Feed the next price, returning `(MACD, Signal, Histogram)` once the
+    /// signal line has warmed up (from `prices[slow + signal]` onwards).
+    pub fn update(&mut self, price: f32) -> Option<(f32, f32, f32)> {
+        self.window.push_back(price);
+        if self.window.len() > self.slow + 1 { self.window.pop_front(); }
+        if self.window.len() < self.slow + 1 { return None; }
+        let window: Vec<f32> = self.window.iter().cloned().collect();
+        let emas_fast = ema::run(window.to_vec(), self.fast);
+        let emas_slow = ema::run(window, self.slow);
+        let macd = emas_fast[emas_fast.len()-1] - emas_slow[emas_slow.len()-1];
+        return self.signal.update(macd).map(|signal| (macd, signal, macd - signal));
+    }
+}
+
+/// Kind of momentum shift flagged by `histogram_reversals`.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum HistReversalKind {
+    /// Histogram peaked and started falling (bullish momentum fading).
+    PeakToFall,
+    /// Histogram bottomed out and started rising (bearish momentum fading).
+    TroughToRise,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct HistReversal {
+    pub index: usize,
+    pub kind: HistReversalKind,
+}
+
+/// Calculate the MACD histogram from the `(MACD, Signal)` output of `run`.
+///
+/// The signal line is shorter than the MACD line, so the histogram is
+/// aligned to the signal line: `histogram[i] = macd[i + offset] - signal[i]`
+/// where `offset = macd.len() - signal.len()`.
+///
+/// ### Example
+/// ```
+/// let (macd, signal) = macd::run(prices);
+/// macd::histogram(&macd, &signal);
+/// ```
+pub fn histogram(macd: &[f32], signal: &[f32]) -> Vec<f32> {
+    if signal.len() > macd.len() { panic!("Length mismatch. `signal` contains {} entries, but `macd` only contains {}.", signal.len(), macd.len()); }
+    let offset = macd.len() - signal.len();
+    return signal.iter().enumerate().map(|(i, s)| macd[i+offset] - s).collect();
+}
+
+/// Detect momentum shifts in a MACD histogram before the MACD crosses its
+/// signal line.
+///
+/// ### Usage
+/// A histogram that stops growing and turns down (a local peak) warns that
+/// bullish momentum is fading, even while the MACD is still above its signal
+/// line. Conversely, a local trough warns that bearish momentum is fading.
+///
+/// NOTE: `index` is the index within `histogram` of the peak/trough itself.
+/// Only strict turns are flagged, so a flat top or bottom (equal
+/// consecutive readings) is not reported.
+///
+/// # Arguments
+/// * `histogram` - `&[f32]` containing MACD histogram values
+///
+/// ### Example
+/// ```
+/// let (macd, signal) = macd::run(prices);
+/// macd::histogram_reversals(&macd::histogram(&macd, &signal));
+/// ```
+pub fn histogram_reversals(histogram: &[f32]) -> Vec<HistReversal> {
+    let mut reversals: Vec<HistReversal> = Vec::new();
+    for i in 1..histogram.len().saturating_sub(1) {
+        let prev = histogram[i-1];
+        let cur = histogram[i];
+        let next = histogram[i+1];
+        if cur > prev && next < cur {
+            reversals.push(HistReversal { index: i, kind: HistReversalKind::PeakToFall });
+        } else if cur < prev && next > cur {
+            reversals.push(HistReversal { index: i, kind: HistReversalKind::TroughToRise });
+        }
+    }
+    return reversals;
+}
+
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stock::testutil::geometric_brownian;
 
     #[test]
     fn test_run_simple() {
@@ -121,9 +267,74 @@ mod tests {
             ));
     }
 
+    #[test]
+    fn test_macd_state_matches_run() {
+        let prices = geometric_brownian(7, 200, 0.0, 0.02, 100.0);
+        let (macds, signal) = run(prices.to_vec());
+        let hist = histogram(&macds, &signal);
+
+        let mut state = MacdState::new(12, 26, 9);
+        let mut streamed: Vec<(f32, f32, f32)> = Vec::new();
+        for (i, &p) in prices.iter().enumerate() {
+            match state.update(p) {
+                Some(v) => streamed.push(v),
+                None => assert_eq!(i < 26 + 9, true),
+            };
+        }
+        let expected: Vec<(f32, f32, f32)> = (0..signal.len()).map(|i| (macds[i+9], signal[i], hist[i])).collect();
+        assert_eq!(streamed, expected);
+    }
+
+    #[test]
+    fn test_histogram() {
+        assert_eq!(histogram(&[1.0, 2.0, 3.0, 4.0], &[1.5, 3.5]), vec![1.5, 0.5]);
+    }
+
+    #[test]
+    fn test_histogram_reversals() {
+        let hist = vec![-0.5, 0.2, 0.8, 1.1, 0.6, 0.1, -0.4, -0.9, -0.7, -0.2];
+        assert_eq!(
+            histogram_reversals(&hist),
+            vec![
+                HistReversal { index: 3, kind: HistReversalKind::PeakToFall },
+                HistReversal { index: 7, kind: HistReversalKind::TroughToRise },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_histogram_reversals_monotonic() {
+        assert_eq!(histogram_reversals(&[0.1, 0.2, 0.3, 0.4]), vec![]);
+        assert_eq!(histogram_reversals(&[0.1]), vec![]);
+    }
+
     #[test]
     #[should_panic(expected = "Not enough entries to calculate the EMA. Received 5, but required 27 (26+1).")]
     fn test_run_not_enough_elements() {
         run(vec![10.0, 10.0, 15.0, 20.0, 20.0]);
     }
+
+    #[test]
+    #[should_panic(expected = "Invalid periods for MACD. Received fast 0 and slow 26, but required 0 < fast < slow.")]
+    fn test_run_with_zero_fast() {
+        run_with(vec![10.0; 30], 0, 26, 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid periods for MACD. Received fast 26 and slow 12, but required 0 < fast < slow.")]
+    fn test_run_with_fast_above_slow() {
+        run_with(vec![10.0; 30], 26, 12, 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid periods for MACD. Received fast 12 and slow 12, but required 0 < fast < slow.")]
+    fn test_run_with_fast_equal_slow() {
+        run_with(vec![10.0; 30], 12, 12, 9);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid signal period for MACD. Received 0, but required at least 1.")]
+    fn test_run_with_zero_signal() {
+        run_with(vec![10.0; 30], 12, 26, 0);
+    }
 }
\ No newline at end of file
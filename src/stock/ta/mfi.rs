@@ -0,0 +1,106 @@
+use crate::stock::ta::Indicator;
+
+/// Calculate the money flow index (MFI) based on a `&[(f32, f32, f32)]` of
+/// price data and a `&[u64]` of volume data.
+///
+/// ### Definition
+/// An oscillator -- bounded between [0-100] -- often described as a
+/// volume-weighted RSI. It compares the money flowing in on bars where the
+/// typical price rose with the money flowing out on bars where it fell.
+///
+/// ### Formula
+/// `tp = (h + l + c) / 3`
+///
+/// `mf = tp * v`
+///
+/// `mfi = 100 - (100 / (1 + (pmf / nmf)))`
+///
+/// - `tp`: typical price of a bar
+/// - `mf`: raw money flow of a bar
+/// - `pmf`: sum of `mf` over the period where `tp` rose
+/// - `nmf`: sum of `mf` over the period where `tp` fell
+///
+/// NOTE: Bars where the typical price is unchanged count towards neither
+/// flow. A window with no money flow in either direction (flat prices or
+/// zero volume) reads 50. The first value corresponds to `prices[period]`.
+///
+/// ### Usage
+/// Like the RSI, readings above 80 are considered overbought and readings
+/// below 20 oversold. Divergence between the MFI and price is a common
+/// reversal signal.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `volumes` - `&[u64]` containing volume data for a period of time
+/// * `period` - Number of money flows to sum (commonly 14)
+///
+/// ### Example
+/// ```
+/// mfi::run(&prices, &volumes, 14);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/m/mfi.asp
+pub fn run(prices: &[(f32, f32, f32)], volumes: &[u64], period: usize) -> Vec<f32> {
+    if prices.len() != volumes.len() { panic!("Length mismatch. `prices` contains {} entries, but `volumes` contains {}.", prices.len(), volumes.len()); }
+    if period == 0 { panic!("Invalid period for MFI. Received 0, but required at least 1."); }
+    if prices.len() < period+1 { panic!("Not enough entries to calculate the MFI. Received {}, but required {} (period+1).", prices.len(), period+1); }
+    let tps: Vec<f32> = prices.iter().map(|&(c, l, h)| (h + l + c) / 3.0).collect();
+    let mut positive: Vec<f64> = Vec::new();
+    let mut negative: Vec<f64> = Vec::new();
+    for i in 1..tps.len() {
+        let flow = tps[i] as f64 * volumes[i] as f64;
+        positive.push(if tps[i] > tps[i-1] { flow } else { 0.0 });
+        negative.push(if tps[i] < tps[i-1] { flow } else { 0.0 });
+    }
+    let mut mfis: Vec<f32> = Vec::new();
+    for i in period..positive.len()+1 {
+        let pmf: f64 = positive[i-period..i].iter().sum();
+        let nmf: f64 = negative[i-period..i].iter().sum();
+        if pmf == 0.0 && nmf == 0.0 { mfis.push(50.0); continue; }
+        mfis.push((100.0 - (100.0 / (1.0 + (pmf / nmf)))) as f32);
+    }
+    return mfis;
+}
+
+/// `Indicator` implementation of the money flow index's `run`.
+pub struct Mfi {
+    pub period: usize,
+}
+
+impl Indicator for Mfi {
+    type Input = (Vec<(f32, f32, f32)>, Vec<u64>);
+    type Output = Vec<f32>;
+
+    fn compute(&self, data: &Self::Input) -> Self::Output { return run(&data.0, &data.1, self.period); }
+    fn warmup(&self) -> usize { return self.period; }
+    fn requires_volume(&self) -> bool { return true; }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        // tp = [10, 12, 11, 13]: flows +1200, -1100, +2600 so pmf / nmf = 3800 / 1100
+        let prices = vec![(10.0, 9.0, 11.0), (12.0, 11.0, 13.0), (11.0, 10.0, 12.0), (13.0, 12.0, 14.0)];
+        let volumes = vec![100, 100, 100, 200];
+        assert_eq!(run(&prices, &volumes, 3), vec![77.55102]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the MFI. Received 2, but required 4 (period+1).")]
+    fn test_run_not_enough_elements() {
+        run(&[(10.0, 9.0, 11.0), (12.0, 11.0, 13.0)], &[100, 100], 3);
+    }
+
+    #[test]
+    fn test_run_flat_window() {
+        // No typical price changes, then a rise on zero volume: no money flows either way
+        let prices = vec![(10.0, 9.0, 11.0), (10.0, 9.0, 11.0), (10.0, 9.0, 11.0), (12.0, 11.0, 13.0)];
+        let volumes = vec![100, 100, 100, 0];
+        assert_eq!(run(&prices, &volumes, 2), vec![50.0, 50.0]);
+    }
+}
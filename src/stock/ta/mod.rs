@@ -1,7 +1,77 @@
+pub mod alignment;
+pub mod alligator;
+pub mod atr;
 pub mod bollinger_band;
+pub mod breakout;
+pub mod cci;
+pub mod chaikin_volatility;
+pub mod chande_kroll;
+pub mod cross;
+pub mod cross_check;
+pub mod decay;
+pub mod divergence;
 pub mod ema;
+pub mod envelope;
+pub mod extract;
+pub mod fractals;
+pub mod gann_hl;
+pub mod gaps;
+pub mod gmma;
+pub mod ht_period;
+pub mod impulse;
+pub mod indicator;
+pub mod linreg;
+pub mod ma_envelope;
 pub mod macd;
+pub mod mfi;
+pub mod momentum;
 pub mod obv;
+pub mod pairs;
+pub mod pivots;
+pub mod regime;
+pub mod relative_strength;
+pub mod renko;
+pub mod ribbon;
+pub mod rolling;
 pub mod rsi;
 pub mod sma;
+pub mod source;
+pub mod smma;
+pub mod smoothing;
+pub mod stats;
 pub mod stochastic_oscillator;
+pub mod threshold;
+pub mod timeframe;
+pub mod transform;
+pub mod trend;
+pub mod trima;
+pub mod util;
+pub mod vwap;
+pub mod williams_r;
+pub mod wma;
+
+pub use self::alignment::{align, Alignment, GapPolicy, Series};
+pub use self::breakout::{breakouts, Breakout};
+pub use self::cross::{crossovers, crossovers_aligned, zero_crosses, CrossDirection, Crossover, ZeroCross};
+pub use self::decay::decayed_average;
+pub use self::divergence::{divergences, Divergence, DivergenceKind};
+pub use self::envelope::envelope;
+pub use self::extract::{close_low_highs, closes, highs, lows, medians, opens};
+pub use self::gaps::{gaps, Gap, GapKind};
+pub use self::indicator::{Indicator, IndicatorId};
+pub use self::momentum::{anchored_momentum, roc};
+pub use self::pairs::{ratio, spread, spread_aligned};
+pub use self::pivots::{find_pivots, Pivot, PivotKind};
+pub use self::regime::{volatility_regime, Regime};
+pub use self::relative_strength::relative_strength;
+pub use self::ribbon::{ema_ribbon, ribbon_compression};
+pub use self::rolling::rolling_quantile;
+pub use self::rsi::volume_rsi;
+pub use self::smoothing::{signal_line, smooth, Smoothing};
+pub use self::source::PriceSource;
+pub use self::stats::{beta, correlation, correlation_aligned, kurtosis, max_drawdown, returns, sharpe, skewness, volatility, ReturnKind};
+pub use self::threshold::threshold_duration;
+pub use self::timeframe::project_higher_tf;
+pub use self::transform::{delta, Rounding};
+pub use self::trend::{classify_trend, TrendClass};
+pub use self::util::{InputPolicy, TaError};
@@ -0,0 +1,102 @@
+/// Calculate the percent change of every bar of a `&[f32]` of price data
+/// from a fixed anchor bar, rather than over a fixed lookback.
+///
+/// ### Formula
+/// `momentum = ((p / p_anchor) - 1) * 100`
+///
+/// NOTE: Bars before the anchor have no value and are left out, so the
+/// first value (always 0) corresponds to `prices[anchor]`.
+///
+/// ### Usage
+/// The "performance since X" view, e.g. year-to-date returns anchored at
+/// the first bar of the year, or the move since an earnings release.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `anchor` - Index of the bar to measure from
+///
+/// ### Example
+/// ```
+/// ta::anchored_momentum(&prices, year_start_index);
+/// ```
+pub fn anchored_momentum(prices: &[f32], anchor: usize) -> Vec<f32> {
+    if anchor >= prices.len() { panic!("Anchor index {} out of bounds. `prices` contains {} entries.", anchor, prices.len()); }
+    let base = prices[anchor];
+    if base == 0.0 { panic!("Invalid anchor price. Received 0 at index {}, but required a non-zero price.", anchor); }
+    return prices[anchor..].iter().map(|p| ((p / base) - 1.0) * 100.0).collect();
+}
+
+/// Calculate the rate of change (ROC) of a `&[f32]` of values over a fixed
+/// lookback.
+///
+/// ### Formula
+/// `roc = ((v / v_n) - 1) * 100`
+///
+/// - `v_n`: value `n` (`period`) bars earlier
+///
+/// NOTE: The first value corresponds to `values[period]`. A change from a
+/// value of 0 is undefined and emitted as `NaN`.
+///
+/// # Arguments
+/// * `values` - `&[f32]` containing values for a period of time
+/// * `period` - Number of bars to look back
+///
+/// ### Example
+/// ```
+/// ta::roc(&prices, 12);
+/// ```
+pub fn roc(values: &[f32], period: usize) -> Vec<f32> {
+    if period == 0 { panic!("Invalid period for ROC. Received 0, but required at least 1."); }
+    if values.len() < period+1 { panic!("Not enough entries to calculate the ROC. Received {}, but required {} (period+1).", values.len(), period+1); }
+    let mut rocs: Vec<f32> = Vec::new();
+    for i in period..values.len() {
+        let base = values[i-period];
+        if base == 0.0 {
+            rocs.push(f32::NAN);
+        } else {
+            rocs.push(((values[i] / base) - 1.0) * 100.0);
+        }
+    }
+    return rocs;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchored_momentum_from_start() {
+        let prices = vec![100.0, 110.0, 95.0, 120.0];
+        let expected: Vec<f32> = prices.iter().map(|p| ((p - 100.0) / 100.0) * 100.0).collect();
+        let momentum = anchored_momentum(&prices, 0);
+        assert_eq!(momentum.len(), prices.len());
+        for i in 0..momentum.len() {
+            assert_eq!((momentum[i] - expected[i]).abs() < 1e-4, true);
+        }
+    }
+
+    #[test]
+    fn test_anchored_momentum_later_bar() {
+        assert_eq!(anchored_momentum(&[100.0, 50.0, 75.0, 25.0], 1), vec![0.0, 50.0, -50.0]);
+    }
+
+    #[test]
+    fn test_roc() {
+        let rocs = roc(&[100.0, 50.0, 110.0, 75.0, 0.0, 75.0, 10.0], 2);
+        assert_eq!(rocs[..3].to_vec(), vec![10.000002, 50.0, -100.0]);
+        assert_eq!(rocs[3], 0.0);
+        assert_eq!(rocs[4].is_nan(), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the ROC. Received 2, but required 3 (period+1).")]
+    fn test_roc_not_enough_elements() {
+        roc(&[100.0, 110.0], 2);
+    }
+
+    #[test]
+    #[should_panic(expected = "Anchor index 3 out of bounds. `prices` contains 2 entries.")]
+    fn test_anchored_momentum_out_of_bounds() {
+        anchored_momentum(&[100.0, 110.0], 3);
+    }
+}
@@ -1,3 +1,5 @@
+use crate::stock::ta::{divergence, ema, Divergence, Indicator};
+
 /// Calculate the on-balance volume (OBV) based on a `Vec<f32>` of price data
 /// and a `Vec<u32>` of volume data.
 /// 
@@ -35,12 +37,12 @@
 /// 
 /// #### Resources
 /// - https://www.investopedia.com/terms/o/onbalancevolume.asp
-pub fn run(prices: Vec<f32>, volume: Vec<u32>) -> Vec<i32> {
+pub fn run(prices: Vec<f32>, volume: Vec<u32>) -> Vec<i64> {
     if prices.len() != volume.len() {panic!("Length mismatch. `prices` contains {} entries, but `volume` contains {}.", prices.len(), volume.len())}
     if prices.len() < 2 { panic!("Not enough entries to calculate OBV. Received {}, but required 2.", prices.len()); }
-    let mut obvs: Vec<i32> = Vec::new();
+    let mut obvs: Vec<i64> = Vec::new();
     let mut obv_prev = 0;
-    let mut price_prev = match prices.get(0) {
+    let mut price_prev = match prices.first() {
         Some(&v) => v,
         None => 0.0
     };
@@ -53,9 +55,9 @@ pub fn run(prices: Vec<f32>, volume: Vec<u32>) -> Vec<i32> {
             Some(&v) => v,
             None => 0,
         };
-        let mut v_final: i32 = 0;
-        if price > price_prev { v_final = v as i32;}
-        if price < price_prev { v_final = -1 * v as i32;}
+        let mut v_final: i64 = 0;
+        if price > price_prev { v_final = v as i64;}
+        if price < price_prev { v_final = -(v as i64);}
         let obv = obv_prev + v_final;
         obvs.push(obv);
         obv_prev = obv;
@@ -64,9 +66,71 @@ pub fn run(prices: Vec<f32>, volume: Vec<u32>) -> Vec<i32> {
     return obvs;
 }
 
+/// Calculate the signal line of a `&[i64]` of OBV values: an EMA of the OBV.
+///
+/// A rising OBV above its signal line confirms buying pressure, while the
+/// OBV crossing below it warns the move is losing volume support.
+///
+/// NOTE: The first value corresponds to `obv[period]`.
+///
+/// # Arguments
+/// * `obv` - `&[i64]` containing OBV values (e.g. from `run`)
+/// * `period` - Number of periods to average
+///
+/// ### Example
+/// ```
+/// obv::signal(&obv::run(prices, volume), 20);
+/// ```
+pub fn signal(obv: &[i64], period: usize) -> Vec<f32> {
+    return ema::run(obv.iter().map(|&v| v as f32).collect(), period);
+}
+
+/// Find the divergences between a `&[f32]` of price data and a `&[i64]` of
+/// OBV values (see `divergence::divergences`).
+///
+/// Price making a new high without the OBV (volume not confirming the move)
+/// is one of the main uses of the OBV.
+///
+/// NOTE: `obv` may be shorter than `prices` (`run` has no value for the
+/// first bar), in which case it is aligned with the end of `prices`. The
+/// indices of the returned divergences refer to `prices`.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `obv` - `&[i64]` containing OBV values (e.g. from `run`)
+///
+/// ### Example
+/// ```
+/// obv::divergences(&prices, &obv::run(prices.to_vec(), volume));
+/// ```
+pub fn divergences(prices: &[f32], obv: &[i64]) -> Vec<Divergence> {
+    if obv.len() > prices.len() { panic!("Length mismatch. `obv` contains {} entries, but `prices` only contains {}.", obv.len(), prices.len()); }
+    let offset = prices.len() - obv.len();
+    let values: Vec<f32> = obv.iter().map(|&v| v as f32).collect();
+    let mut found = divergence::divergences(&prices[offset..], &values);
+    for d in found.iter_mut() {
+        d.first += offset;
+        d.second += offset;
+    }
+    return found;
+}
+
+/// `Indicator` implementation of the on-balance volume's `run`.
+pub struct Obv {}
+
+impl Indicator for Obv {
+    type Input = (Vec<f32>, Vec<u32>);
+    type Output = Vec<i64>;
+
+    fn compute(&self, data: &Self::Input) -> Self::Output { return run(data.0.to_vec(), data.1.to_vec()); }
+    fn warmup(&self) -> usize { return 1; }
+    fn requires_volume(&self) -> bool { return true; }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stock::ta::DivergenceKind;
 
     #[test]
     fn test_run_simple() {
@@ -78,6 +142,38 @@ mod tests {
         assert_eq!(run(vec![10.0, 15.0, 20.0, 15.0, 10.0], vec![100, 500, 300, 1500, 200]), vec![500, 800, -700, -900]);
     }
 
+    #[test]
+    fn test_run_large_volume() {
+        // Larger than `i32::MAX` in total
+        let obvs = run(vec![10.0, 11.0, 12.0], vec![0, 2_000_000_000, 2_000_000_000]);
+        assert_eq!(obvs, vec![2_000_000_000, 4_000_000_000]);
+    }
+
+    #[test]
+    fn test_signal() {
+        let obvs = vec![100, 200, 300, 400];
+        assert_eq!(signal(&obvs, 3), ema::run(vec![100.0, 200.0, 300.0, 400.0], 3));
+        assert_eq!(signal(&obvs, 3).len(), 1);
+    }
+
+    #[test]
+    fn test_divergences_lower_obv_high() {
+        // Price makes a higher high on thinner up volume than the first push
+        let prices = vec![10.0, 11.0, 12.0, 13.0, 12.0, 11.5, 12.0, 12.5, 14.0, 13.0, 12.0];
+        let volume = vec![0, 1000, 1000, 1000, 800, 800, 200, 200, 200, 900, 900];
+        let obvs = run(prices.to_vec(), volume);
+        assert_eq!(obvs[2] > obvs[7], true);
+        assert_eq!(divergences(&prices, &obvs), vec![
+            Divergence { first: 3, second: 8, kind: DivergenceKind::Bearish },
+        ]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `obv` contains 3 entries, but `prices` only contains 2.")]
+    fn test_divergences_length_mismatch() {
+        divergences(&[10.0, 11.0], &[100, 200, 300]);
+    }
+
     #[test]
     #[should_panic(expected = "Length mismatch. `prices` contains 2 entries, but `volume` contains 1.")]
     fn test_run_length_mismatch() {
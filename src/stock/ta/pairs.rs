@@ -0,0 +1,138 @@
+use crate::stock::ta::{alignment, GapPolicy};
+
+/// Calculate the spread `a - b` between two `&[f32]` of price data.
+///
+/// ### Usage
+/// In pairs trading the spread between two related securities (e.g. two
+/// stocks in the same sector) is expected to mean-revert. A spread far from
+/// its average (e.g. by its z-score) is traded back towards it by buying
+/// one leg and selling the other.
+///
+/// NOTE: Both series must be aligned and of equal length.
+///
+/// # Arguments
+/// * `a` - `&[f32]` containing prices of the first leg
+/// * `b` - `&[f32]` containing prices of the second leg
+///
+/// ### Example
+/// ```
+/// ta::spread(&ko, &pep);
+/// ```
+pub fn spread(a: &[f32], b: &[f32]) -> Vec<f32> {
+    if a.len() != b.len() { panic!("Length mismatch. `a` contains {} entries, but `b` contains {}.", a.len(), b.len()); }
+    return a.iter().zip(b.iter()).map(|(x, y)| x - y).collect();
+}
+
+/// Calculate the spread `a - b` between two aligned `&[Option<f32>]` of
+/// values whose warm-ups differ (e.g. padded with `align`).
+///
+/// NOTE: With `GapPolicy::PropagateNone` the output is aligned with the
+/// inputs and is `None` wherever either input has a gap. With
+/// `GapPolicy::SkipBar` those bars are left out, so the output only holds
+/// the bars where both inputs have a value.
+///
+/// # Arguments
+/// * `a` - `&[Option<f32>]` containing values of the first leg
+/// * `b` - `&[Option<f32>]` containing values of the second leg
+/// * `policy` - How bars with a gap in either input are treated
+///
+/// ### Example
+/// ```
+/// ta::spread_aligned(&fast, &slow, GapPolicy::PropagateNone);
+/// ```
+pub fn spread_aligned(a: &[Option<f32>], b: &[Option<f32>], policy: GapPolicy) -> Vec<Option<f32>> {
+    if a.len() != b.len() { panic!("Length mismatch. `a` contains {} entries, but `b` contains {}.", a.len(), b.len()); }
+    let mut spreads: Vec<Option<f32>> = Vec::new();
+    for i in 0..a.len() {
+        match (alignment::present(a[i]), alignment::present(b[i])) {
+            (Some(x), Some(y)) => spreads.push(Some(x - y)),
+            _ => if policy == GapPolicy::PropagateNone { spreads.push(None); },
+        };
+    }
+    return spreads;
+}
+
+/// Calculate the ratio `a / b` between two `&[f32]` of price data.
+///
+/// Unlike the spread, the ratio does not depend on the price levels of the
+/// two legs, so it is preferred for pairs that trade at very different
+/// prices.
+///
+/// NOTE: Both series must be aligned and of equal length, and `b` must not
+/// contain 0.
+///
+/// # Arguments
+/// * `a` - `&[f32]` containing prices of the first leg
+/// * `b` - `&[f32]` containing prices of the second leg
+///
+/// ### Example
+/// ```
+/// ta::ratio(&ko, &pep);
+/// ```
+pub fn ratio(a: &[f32], b: &[f32]) -> Vec<f32> {
+    if a.len() != b.len() { panic!("Length mismatch. `a` contains {} entries, but `b` contains {}.", a.len(), b.len()); }
+    let mut ratios: Vec<f32> = Vec::new();
+    for i in 0..a.len() {
+        if b[i] == 0.0 { panic!("Division by zero. `b` contains 0 at index {}.", i); }
+        ratios.push(a[i] / b[i]);
+    }
+    return ratios;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::ta::{align, correlation, sma, Alignment};
+
+    #[test]
+    fn test_spread() {
+        assert_eq!(spread(&[10.0, 12.0, 11.0], &[8.0, 9.0, 12.0]), vec![2.0, 3.0, -1.0]);
+    }
+
+    #[test]
+    fn test_ratio() {
+        assert_eq!(ratio(&[10.0, 12.0, 11.0], &[5.0, 8.0, 22.0]), vec![2.0, 1.5, 0.5]);
+    }
+
+    #[test]
+    fn test_ratio_constant_for_scaled_pair() {
+        let a = vec![10.0, 11.0, 12.5, 11.5];
+        let b: Vec<f32> = a.iter().map(|p| p * 4.0).collect();
+        assert_eq!(ratio(&a, &b), vec![0.25; 4]);
+        // The legs move together, but the spread still drifts with price
+        assert_eq!(correlation(&a, &b, 4)[0] > 0.99, true);
+        assert_eq!(spread(&a, &b), vec![-30.0, -33.0, -37.5, -34.5]);
+    }
+
+    #[test]
+    fn test_spread_aligned_mismatched_warmups() {
+        let prices = [10.0, 11.0, 12.0, 13.0, 14.0, 15.0];
+        let fast = align(sma::run(prices.to_vec(), 2), prices.len(), Alignment::PaddedFront);
+        let slow = align(sma::run(prices.to_vec(), 4), prices.len(), Alignment::PaddedFront);
+        assert_eq!(
+            spread_aligned(&fast, &slow, GapPolicy::PropagateNone),
+            vec![None, None, None, Some(1.0), Some(1.0), Some(1.0)]
+        );
+        assert_eq!(spread_aligned(&fast, &slow, GapPolicy::SkipBar), vec![Some(1.0), Some(1.0), Some(1.0)]);
+    }
+
+    #[test]
+    fn test_spread_aligned_nan() {
+        let a = vec![Some(10.0), Some(f32::NAN), Some(12.0)];
+        let b = vec![Some(8.0), Some(9.0), None];
+        assert_eq!(spread_aligned(&a, &b, GapPolicy::PropagateNone), vec![Some(2.0), None, None]);
+        assert_eq!(spread_aligned(&a, &b, GapPolicy::SkipBar), vec![Some(2.0)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Division by zero. `b` contains 0 at index 1.")]
+    fn test_ratio_zero() {
+        ratio(&[10.0, 12.0], &[5.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `a` contains 2 entries, but `b` contains 1.")]
+    fn test_spread_length_mismatch() {
+        spread(&[10.0, 12.0], &[5.0]);
+    }
+}
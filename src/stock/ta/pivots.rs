@@ -0,0 +1,102 @@
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum PivotKind {
+    /// Swing high.
+    High,
+    /// Swing low.
+    Low,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct Pivot {
+    pub index: usize,
+    pub kind: PivotKind,
+}
+
+/// Find the pivot (swing) highs and lows of a `&[f32]` of price data.
+///
+/// ### Definition
+/// A pivot high is a price greater than the `strength` prices on either
+/// side of it, and a pivot low a price less than them.
+///
+/// ##### Plateaus
+/// A run of equal prices (e.g. a flat top) is treated as a single bar: it
+/// is a pivot if it is greater (or less) than the `strength` prices before
+/// its first bar and after its last bar. The pivot is always reported at
+/// the *first* bar of the plateau, so a flat top is neither missed nor
+/// counted twice.
+///
+/// NOTE: A pivot is only confirmed `strength` bars after it ends, so the
+/// first and last `strength` bars can never be pivots.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `strength` - Number of bars on each side a pivot must exceed (commonly 2 to 5)
+///
+/// ### Example
+/// ```
+/// ta::find_pivots(&highs, 3);
+/// ```
+pub fn find_pivots(prices: &[f32], strength: usize) -> Vec<Pivot> {
+    if strength == 0 { panic!("Invalid strength for pivots. Received 0, but required at least 1."); }
+    let mut pivots: Vec<Pivot> = Vec::new();
+    let mut start = 0;
+    while start < prices.len() {
+        let value = prices[start];
+        // Last bar of the plateau starting at `start`
+        let mut end = start;
+        while end + 1 < prices.len() && prices[end + 1] == value { end += 1; }
+        if start >= strength && end + strength < prices.len() {
+            let before = &prices[start-strength..start];
+            let after = &prices[end+1..end+1+strength];
+            if before.iter().chain(after.iter()).all(|&p| p < value) {
+                pivots.push(Pivot { index: start, kind: PivotKind::High });
+            } else if before.iter().chain(after.iter()).all(|&p| p > value) {
+                pivots.push(Pivot { index: start, kind: PivotKind::Low });
+            }
+        }
+        start = end + 1;
+    }
+    return pivots;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_find_pivots() {
+        let prices = vec![10.0, 11.0, 13.0, 12.0, 11.0, 9.0, 10.0, 11.0];
+        assert_eq!(find_pivots(&prices, 2), vec![
+            Pivot { index: 2, kind: PivotKind::High },
+            Pivot { index: 5, kind: PivotKind::Low },
+        ]);
+    }
+
+    #[test]
+    fn test_find_pivots_plateau_high() {
+        // Three equal highs form one pivot at the first bar of the plateau
+        let prices = vec![10.0, 11.0, 14.0, 14.0, 14.0, 12.0, 11.0];
+        assert_eq!(find_pivots(&prices, 2), vec![Pivot { index: 2, kind: PivotKind::High }]);
+    }
+
+    #[test]
+    fn test_find_pivots_plateau_low() {
+        let prices = vec![12.0, 11.0, 9.0, 9.0, 10.0, 11.0];
+        assert_eq!(find_pivots(&prices, 2), vec![Pivot { index: 2, kind: PivotKind::Low }]);
+    }
+
+    #[test]
+    fn test_find_pivots_step_is_not_pivot() {
+        // A step up to a new level is not a swing
+        let prices = vec![10.0, 11.0, 12.0, 12.0, 12.0, 13.0, 14.0];
+        assert_eq!(find_pivots(&prices, 2), Vec::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid strength for pivots. Received 0, but required at least 1.")]
+    fn test_find_pivots_zero_strength() {
+        find_pivots(&[10.0], 0);
+    }
+}
@@ -0,0 +1,92 @@
+use crate::stock::ta::{atr, rolling_quantile};
+
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Regime {
+    /// ATR below the 25th percentile of its recent history.
+    Low,
+    /// ATR between the 25th and 75th percentiles of its recent history.
+    Normal,
+    /// ATR above the 75th percentile of its recent history.
+    High,
+}
+
+/// Percentile of the ATR's history below which volatility is `Regime::Low`.
+pub const LOW_PERCENTILE: f32 = 0.25;
+/// Percentile of the ATR's history above which volatility is `Regime::High`.
+pub const HIGH_PERCENTILE: f32 = 0.75;
+
+/// Classify the volatility regime of every bar of a `&[(f32, f32, f32)]` of
+/// price data.
+///
+/// ### Definition
+/// The ATR of the current bar is compared with the ATRs of the last
+/// `period` bars (including the current one): above their 75th percentile
+/// is `High`, below their 25th percentile is `Low`, and anything in between
+/// (including the percentiles themselves) is `Normal`.
+///
+/// NOTE: `period` is used for both the ATR and the lookback window, so the
+/// first value corresponds to `prices[2 * period - 1]`.
+///
+/// ### Usage
+/// Volatility clusters, so the regime is useful to adapt a strategy, e.g.
+/// wider stops and smaller positions in a `High` regime, or only taking
+/// breakouts out of a `Low` regime.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `period` - Number of periods for the ATR and its lookback window
+///
+/// ### Example
+/// ```
+/// ta::volatility_regime(&prices, 14);
+/// ```
+pub fn volatility_regime(prices: &[(f32, f32, f32)], period: usize) -> Vec<Regime> {
+    if period == 0 || prices.len() < 2 * period { panic!("Not enough entries to calculate the volatility regime. Received {}, but required {} (2*period).", prices.len(), 2 * period); }
+    let atrs = atr::run(prices.to_vec(), period);
+    let lows = rolling_quantile(&atrs, period, LOW_PERCENTILE);
+    let highs = rolling_quantile(&atrs, period, HIGH_PERCENTILE);
+    let mut regimes: Vec<Regime> = Vec::new();
+    for k in 0..lows.len() {
+        let current = atrs[period - 1 + k];
+        if current > highs[k] {
+            regimes.push(Regime::High);
+        } else if current < lows[k] {
+            regimes.push(Regime::Low);
+        } else {
+            regimes.push(Regime::Normal);
+        }
+    }
+    return regimes;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_volatility_regime_burst() {
+        let mut prices: Vec<(f32, f32, f32)> = vec![(100.0, 99.5, 100.5); 30];
+        prices.extend(vec![(100.0, 97.0, 103.0); 3]);
+        let regimes = volatility_regime(&prices, 10);
+        assert_eq!(regimes.len(), prices.len() - 19);
+        // Constant ATR while calm
+        assert_eq!(regimes[..regimes.len()-3].iter().all(|&r| r == Regime::Normal), true);
+        assert_eq!(regimes[regimes.len()-3..].to_vec(), vec![Regime::High; 3]);
+    }
+
+    #[test]
+    fn test_volatility_regime_calming() {
+        let mut prices: Vec<(f32, f32, f32)> = vec![(100.0, 97.0, 103.0); 20];
+        prices.extend(vec![(100.0, 99.5, 100.5); 4]);
+        let regimes = volatility_regime(&prices, 5);
+        assert_eq!(regimes[regimes.len()-1], Regime::Low);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the volatility regime. Received 5, but required 20 (2*period).")]
+    fn test_volatility_regime_not_enough_elements() {
+        volatility_regime(&[(100.0, 99.5, 100.5); 5], 10);
+    }
+}
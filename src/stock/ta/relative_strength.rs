@@ -0,0 +1,88 @@
+/// Calculate the rolling relative strength of a `&[f32]` of asset prices
+/// against a `&[f32]` of benchmark prices (e.g. a sector ETF against SPY).
+///
+/// ### Definition
+/// Compares how much the asset has grown over the last `period` bars with
+/// how much the benchmark has. Not to be confused with the RSI, which only
+/// looks at a single security.
+///
+/// ### Formula
+/// `rs = (1 + r_asset) / (1 + r_benchmark)`
+///
+/// - `r_asset`: return of the asset over the window, `(a / a_prev) - 1`
+/// - `r_benchmark`: return of the benchmark over the window, `(b / b_prev) - 1`
+///
+/// Values above 1 mean the asset outperformed and below 1 underperformed.
+/// Comparing the growth factors rather than the raw returns keeps the value
+/// defined when the benchmark has not moved at all (`r_benchmark = 0`).
+///
+/// NOTE: Both series must be aligned and of equal length. The first value
+/// corresponds to `asset[period]`.
+///
+/// ### Usage
+/// Sector rotation strategies hold the sectors with the highest relative
+/// strength and rotate out of those falling below 1.
+///
+/// # Arguments
+/// * `asset` - `&[f32]` containing prices of the asset
+/// * `benchmark` - `&[f32]` containing prices of the benchmark
+/// * `period` - Number of bars in each window
+///
+/// ### Example
+/// ```
+/// ta::relative_strength(&xlk, &spy, 63);
+/// ```
+pub fn relative_strength(asset: &[f32], benchmark: &[f32], period: usize) -> Vec<f32> {
+    if asset.len() != benchmark.len() { panic!("Length mismatch. `asset` contains {} entries, but `benchmark` contains {}.", asset.len(), benchmark.len()); }
+    if period == 0 || asset.len() < period+1 { panic!("Not enough entries to calculate the relative strength. Received {}, but required {} (period+1).", asset.len(), period+1); }
+    let mut rss: Vec<f32> = Vec::new();
+    for i in period..asset.len() {
+        let (a_prev, b_prev) = (asset[i-period], benchmark[i-period]);
+        if a_prev == 0.0 || b_prev == 0.0 || benchmark[i] == 0.0 {
+            panic!("Invalid price for relative strength at index {}. Received 0, but required a non-zero price.", if a_prev == 0.0 || b_prev == 0.0 { i-period } else { i });
+        }
+        let asset_growth = asset[i] / a_prev;
+        let benchmark_growth = benchmark[i] / b_prev;
+        rss.push(asset_growth / benchmark_growth);
+    }
+    return rss;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_relative_strength_outperforming() {
+        let asset = vec![100.0, 102.0, 105.0, 108.0, 112.0, 115.0];
+        let benchmark = vec![100.0, 100.5, 101.0, 101.0, 101.5, 102.0];
+        let rss = relative_strength(&asset, &benchmark, 3);
+        assert_eq!(rss.len(), 3);
+        assert_eq!(rss.iter().all(|&rs| rs > 1.0), true);
+    }
+
+    #[test]
+    fn test_relative_strength_flat_benchmark() {
+        // A benchmark that did not move leaves the asset's own growth
+        let rss = relative_strength(&[100.0, 90.0, 110.0], &[50.0, 50.0, 50.0], 2);
+        assert_eq!(rss, vec![1.1]);
+    }
+
+    #[test]
+    fn test_relative_strength_underperforming() {
+        let rss = relative_strength(&[100.0, 100.0], &[100.0, 125.0], 1);
+        assert_eq!(rss, vec![0.8]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid price for relative strength at index 0. Received 0, but required a non-zero price.")]
+    fn test_relative_strength_zero_price() {
+        relative_strength(&[100.0, 110.0], &[0.0, 50.0], 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `asset` contains 2 entries, but `benchmark` contains 1.")]
+    fn test_relative_strength_length_mismatch() {
+        relative_strength(&[100.0, 110.0], &[50.0], 1);
+    }
+}
@@ -0,0 +1,173 @@
+use crate::stock::ta::{atr, CrossDirection};
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct RenkoBrick {
+    pub open: f32,
+    pub close: f32,
+    pub direction: CrossDirection,
+}
+
+/// Convert a `Vec<f32>` of close prices into fixed-size Renko bricks.
+///
+/// ### Definition
+/// Renko charts ignore time and only draw a brick once price has moved a
+/// full `brick_size`. A brick in the same direction as the last one starts
+/// at its close; a reversal brick starts at the last brick's open, so price
+/// has to move two bricks' worth against the trend to reverse it. A large
+/// move produces several bricks at once.
+///
+/// NOTE: The first price is the reference the first brick is built from.
+/// A `brick_size` below the `f32` resolution of the prices (e.g. `0.02` at
+/// a price of `600000`) panics rather than drawing zero-width bricks.
+///
+/// ### Usage
+/// Filtering out moves smaller than `brick_size` removes noise and makes
+/// trends and support/resistance levels easier to spot. A change in brick
+/// direction is a trend reversal signal.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing close prices for a period of time
+/// * `brick_size` - Price move required to draw a brick
+///
+/// ### Example
+/// ```
+/// renko::run(prices, 2.0);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/r/renkochart.asp
+pub fn run(prices: Vec<f32>, brick_size: f32) -> Vec<RenkoBrick> {
+    if brick_size.is_nan() || brick_size <= 0.0 { panic!("Invalid brick size. Received {}, but required a value greater than 0.", brick_size); }
+    let mut bricks: Vec<RenkoBrick> = Vec::new();
+    let first = match prices.first() {
+        Some(&v) => v,
+        None => return bricks,
+    };
+    for &price in prices[1..].iter() {
+        push_bricks(&mut bricks, first, price, brick_size);
+    }
+    return bricks;
+}
+
+/// Convert a `Vec<(f32, f32, f32)>` of price data into Renko bricks sized by
+/// the current ATR rather than a fixed brick size.
+///
+/// Each new brick is `atr` wide as of the bar that draws it, so bricks grow
+/// in volatile markets and shrink in calm ones, keeping the number of bricks
+/// drawn per move roughly constant across volatility regimes.
+///
+/// NOTE: The ATR is only available from `prices[atr_period]` onwards, so that
+/// bar's close is the reference the first brick is built from.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `atr_period` - Number of periods for the ATR
+///
+/// ### Example
+/// ```
+/// renko::run_atr(prices, 14);
+/// ```
+pub fn run_atr(prices: Vec<(f32, f32, f32)>, atr_period: usize) -> Vec<RenkoBrick> {
+    let atrs = atr::run(prices.to_vec(), atr_period);
+    // `atrs[0]` corresponds to `prices[atr_period]`
+    let reference = prices[atr_period].0;
+    let mut bricks: Vec<RenkoBrick> = Vec::new();
+    for i in 1..atrs.len() {
+        if atrs[i] > 0.0 { push_bricks(&mut bricks, reference, prices[i+atr_period].0, atrs[i]); }
+    }
+    return bricks;
+}
+
+/// Draw as many bricks as `price` has moved beyond the last brick (or beyond
+/// `reference` before the first brick).
+fn push_bricks(bricks: &mut Vec<RenkoBrick>, reference: f32, price: f32, brick_size: f32) {
+    loop {
+        // Edges a new up/down brick would be drawn from
+        let (top, bottom) = match bricks.last() {
+            Some(b) => (b.open.max(b.close), b.open.min(b.close)),
+            None => (reference, reference),
+        };
+        // A brick too small to move the edge in `f32` would be drawn forever
+        if top + brick_size == top || bottom - brick_size == bottom {
+            panic!("Invalid brick size. Received {}, but required a value large enough to move a price of {}.", brick_size, top);
+        }
+        if price >= top + brick_size {
+            bricks.push(RenkoBrick { open: top, close: top + brick_size, direction: CrossDirection::Up });
+        } else if price <= bottom - brick_size {
+            bricks.push(RenkoBrick { open: bottom, close: bottom - brick_size, direction: CrossDirection::Down });
+        } else {
+            return;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_steady_climb() {
+        let prices: Vec<f32> = (0..21).map(|i| 100.0 + 0.5 * i as f32).collect();
+        let bricks = run(prices, 2.0);
+        // A 10 point climb draws 5 bricks of 2
+        assert_eq!(bricks.len(), 5);
+        assert_eq!(bricks.iter().all(|b| b.direction == CrossDirection::Up), true);
+        assert_eq!(bricks[0], RenkoBrick { open: 100.0, close: 102.0, direction: CrossDirection::Up });
+        assert_eq!(bricks[4], RenkoBrick { open: 108.0, close: 110.0, direction: CrossDirection::Up });
+    }
+
+    #[test]
+    fn test_run_reversal() {
+        // Falling back one brick is not enough to reverse an up brick
+        assert_eq!(run(vec![100.0, 102.5, 100.5], 2.0).len(), 1);
+        assert_eq!(
+            run(vec![100.0, 102.5, 97.0], 2.0),
+            vec![
+                RenkoBrick { open: 100.0, close: 102.0, direction: CrossDirection::Up },
+                RenkoBrick { open: 100.0, close: 98.0, direction: CrossDirection::Down },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_run_not_enough_movement() {
+        assert_eq!(run(vec![100.0, 101.0, 99.0, 100.5], 2.0), vec![]);
+        assert_eq!(run(vec![], 2.0), vec![]);
+    }
+
+    #[test]
+    fn test_run_atr_volatility_regimes() {
+        let mut prices: Vec<(f32, f32, f32)> = Vec::new();
+        // Calm: +1 a bar with a 1 point range
+        for i in 0..20 {
+            let c = 100.0 + i as f32;
+            prices.push((c, c - 0.5, c + 0.5));
+        }
+        // Volatile: +10 a bar with a 10 point range
+        for i in 1..21 {
+            let c = 119.0 + 10.0 * i as f32;
+            prices.push((c, c - 5.0, c + 5.0));
+        }
+        let bricks = run_atr(prices, 5);
+        let sizes: Vec<f32> = bricks.iter().map(|b| (b.close - b.open).abs()).collect();
+        let calm: Vec<&f32> = sizes.iter().filter(|&&s| s < 2.0).collect();
+        assert_eq!(calm.len() > 5, true);
+        assert_eq!(sizes[0] < 2.0, true);
+        assert_eq!(sizes[sizes.len()-1] > 10.0, true);
+        assert_eq!(bricks.iter().all(|b| b.direction == CrossDirection::Up), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid brick size. Received 0, but required a value greater than 0.")]
+    fn test_run_zero_brick_size() {
+        run(vec![100.0], 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid brick size. Received 0.02, but required a value large enough to move a price of 600000.")]
+    fn test_run_brick_below_resolution() {
+        run(vec![600000.0, 600001.0], 0.02);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::stock::ta::ema;
+
+/// Calculate an EMA ribbon: one EMA of a `&[f32]` of price data for each of
+/// the requested periods.
+///
+/// ### Usage
+/// In a strong uptrend the ribbon fans out with the shortest EMA on top and
+/// the longest on the bottom (reversed in a downtrend). Lines twisting
+/// through each other signal a weakening or reversing trend.
+///
+/// ### Alignment
+/// Every line is trimmed to the warm-up of the longest period so that the
+/// same index refers to the same bar in every line. The first value of each
+/// line corresponds to `prices[max(periods)]`.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `periods` - `&[usize]` with the number of periods for each EMA
+///
+/// # Returns
+/// `Vec<Vec<f32>>` containing one line per entry of `periods`, in the same order.
+///
+/// ### Example
+/// ```
+/// ta::ema_ribbon(&prices, &[5, 10, 20, 30, 40, 50]);
+/// ```
+pub fn ema_ribbon(prices: &[f32], periods: &[usize]) -> Vec<Vec<f32>> {
+    let longest = match periods.iter().max() {
+        Some(&p) => p,
+        None => return Vec::new(),
+    };
+    if prices.len() < longest+1 { panic!("Not enough entries to calculate the EMA ribbon. Received {}, but required {} (periods+1).", prices.len(), longest+1); }
+    let mut ribbon: Vec<Vec<f32>> = Vec::new();
+    for &period in periods {
+        let emas = ema::run(prices.to_vec(), period);
+        ribbon.push(emas[longest-period..].to_vec());
+    }
+    return ribbon;
+}
+
+/// Calculate the compression of a ribbon of moving averages (e.g. from
+/// `ema_ribbon` or `gmma::run`) for every bar.
+///
+/// ### Formula
+/// `compression = (max(lines) - min(lines)) / mean(lines)`
+///
+/// The mean of the lines stands in for the price so the spread is
+/// comparable across assets and price levels.
+///
+/// ### Usage
+/// A ribbon squeezing together shows the short- and long-term averages
+/// agreeing on price, which tends to precede an expansion (breakout). Look
+/// for local minima of the compression.
+///
+/// # Arguments
+/// * `ribbon` - `&[Vec<f32>]` containing aligned ribbon lines of equal length
+///
+/// # Returns
+/// `Vec<f32>` containing one compression value per bar of the ribbon.
+///
+/// ### Example
+/// ```
+/// ta::ribbon_compression(&ta::ema_ribbon(&prices, &[5, 10, 20, 30, 40, 50]));
+/// ```
+pub fn ribbon_compression(ribbon: &[Vec<f32>]) -> Vec<f32> {
+    let len = match ribbon.first() {
+        Some(line) => line.len(),
+        None => return Vec::new(),
+    };
+    for line in ribbon.iter() {
+        if line.len() != len { panic!("Length mismatch. Ribbon lines contain {} and {} entries.", len, line.len()); }
+    }
+    let mut compression: Vec<f32> = Vec::new();
+    for i in 0..len {
+        let mut highest = f32::MIN;
+        let mut lowest = f32::MAX;
+        let mut sum = 0.0;
+        for line in ribbon.iter() {
+            highest = highest.max(line[i]);
+            lowest = lowest.min(line[i]);
+            sum += line[i];
+        }
+        let mean = sum / ribbon.len() as f32;
+        if mean == 0.0 {
+            compression.push(0.0);
+        } else {
+            compression.push((highest - lowest) / mean.abs());
+        }
+    }
+    return compression;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ema_ribbon_uptrend() {
+        let prices: Vec<f32> = (0..40).map(|i| 10.0 + i as f32).collect();
+        let ribbon = ema_ribbon(&prices, &[5, 10, 20]);
+        assert_eq!(ribbon.len(), 3);
+        for line in ribbon.iter() {
+            assert_eq!(line.len(), 20);
+        }
+        // Shortest EMA on top, longest on the bottom
+        for i in 0..20 {
+            assert_eq!(ribbon[0][i] > ribbon[1][i], true);
+            assert_eq!(ribbon[1][i] > ribbon[2][i], true);
+        }
+    }
+
+    #[test]
+    fn test_ema_ribbon_alignment() {
+        let prices: Vec<f32> = (0..12).map(|i| 10.0 + i as f32).collect();
+        let ribbon = ema_ribbon(&prices, &[5, 10]);
+        let ema5 = ema::run(prices.to_vec(), 5);
+        assert_eq!(ribbon[0], ema5[5..].to_vec());
+        assert_eq!(ribbon[1], ema::run(prices.to_vec(), 10));
+    }
+
+    #[test]
+    fn test_ribbon_compression_simple() {
+        let ribbon = vec![vec![11.0, 10.0], vec![10.0, 10.0], vec![9.0, 10.0]];
+        assert_eq!(ribbon_compression(&ribbon), vec![0.2, 0.0]);
+    }
+
+    #[test]
+    fn test_ribbon_compression_converging() {
+        // Trend, then a flat consolidation the EMAs converge on, then a breakout
+        let mut prices: Vec<f32> = (0..30).map(|i| 10.0 + i as f32).collect();
+        prices.extend(vec![40.0; 40]);
+        prices.extend((1..11).map(|i| 40.0 + 2.0 * i as f32));
+        let ribbon = ema_ribbon(&prices, &[3, 5, 8, 10]);
+        let compression = ribbon_compression(&ribbon);
+        let mut minimum = 0;
+        for i in 0..compression.len() {
+            if compression[i] < compression[minimum] { minimum = i; }
+        }
+        // Tightest at the end of the consolidation, right before the breakout
+        let breakout = 70 - 10;
+        assert_eq!(minimum, breakout - 1);
+        assert_eq!(compression[0] > compression[minimum], true);
+        assert_eq!(compression[compression.len()-1] > compression[minimum], true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. Ribbon lines contain 2 and 1 entries.")]
+    fn test_ribbon_compression_length_mismatch() {
+        ribbon_compression(&[vec![10.0, 11.0], vec![10.0]]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the EMA ribbon. Received 5, but required 11 (periods+1).")]
+    fn test_ema_ribbon_not_enough_elements() {
+        ema_ribbon(&[10.0, 11.0, 12.0, 13.0, 14.0], &[5, 10]);
+    }
+}
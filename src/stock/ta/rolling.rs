@@ -0,0 +1,139 @@
+use std::cmp::Ordering;
+
+use crate::stock::ta::stats::quantile;
+
+/// Apply `f` to every sliding window of `period` prices in a `&[f32]`.
+///
+/// A building block for custom indicators (e.g. a rolling skew) without
+/// needing a dedicated module. The first value corresponds to
+/// `prices[period - 1]`, like the SMA.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `period` - Number of prices in each window
+/// * `f` - Closure reducing a window (oldest price first) to a single value
+///
+/// ### Example
+/// ```
+/// rolling::rolling(&prices, 20, |w| w.iter().cloned().fold(f32::MIN, f32::max));
+/// ```
+pub fn rolling<F: Fn(&[f32]) -> f32>(prices: &[f32], period: usize, f: F) -> Vec<f32> {
+    if period == 0 || prices.len() < period { panic!("Not enough entries to calculate the rolling window. Received {}, but required {}.", prices.len(), period); }
+    return prices.windows(period).map(f).collect();
+}
+
+/// Calculate the rolling median of a `&[f32]` of price data.
+///
+/// The median is far less sensitive than the SMA to a single bad tick or
+/// spike in a noisy feed. The first value corresponds to `prices[period - 1]`.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `period` - Number of prices in each window
+///
+/// ### Example
+/// ```
+/// rolling::rolling_median(&prices, 20);
+/// ```
+pub fn rolling_median(prices: &[f32], period: usize) -> Vec<f32> {
+    return rolling_quantile(prices, period, 0.5);
+}
+
+/// Calculate the rolling quantile `q` (between 0 and 1) of a `&[f32]` of
+/// price data, using linear interpolation between the closest ranks.
+///
+/// ### Complexity
+/// A sorted copy of the window is kept up to date as it slides: each step
+/// finds the outgoing and incoming prices by binary search (O(log p)) and
+/// shifts the window to remove/insert them (O(p) moves of a contiguous
+/// `Vec`, which is fast for typical periods). That is O(n * p) without the
+/// O(n * p log p) of sorting every window from scratch.
+///
+/// NOTE: Prices are ordered by `f32::total_cmp`, so a positive `NaN` sorts after
+/// every other price and a negative `NaN` before every other price.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `period` - Number of prices in each window
+/// * `q` - Quantile to calculate (e.g. `0.9` for the 90th percentile)
+///
+/// ### Example
+/// ```
+/// ta::rolling_quantile(&prices, 20, 0.9);
+/// ```
+pub fn rolling_quantile(prices: &[f32], period: usize, q: f32) -> Vec<f32> {
+    if period == 0 || prices.len() < period { panic!("Not enough entries to calculate the rolling quantile. Received {}, but required {}.", prices.len(), period); }
+    let cmp = |a: &f32, b: &f32| -> Ordering { return a.total_cmp(b); };
+    let mut window: Vec<f32> = prices[0..period].to_vec();
+    window.sort_by(cmp);
+    let mut quantiles: Vec<f32> = vec![quantile(&window, q)];
+    for i in period..prices.len() {
+        let outgoing = prices[i-period];
+        match window.binary_search_by(|v| cmp(v, &outgoing)) {
+            Ok(j) => { window.remove(j); },
+            Err(_) => panic!("Could not find entry in the rolling window."),
+        };
+        let incoming = prices[i];
+        let j = match window.binary_search_by(|v| cmp(v, &incoming)) {
+            Ok(j) => j,
+            Err(j) => j,
+        };
+        window.insert(j, incoming);
+        quantiles.push(quantile(&window, q));
+    }
+    return quantiles;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::ta::sma;
+
+    #[test]
+    fn test_rolling_sma() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let smas = rolling(&prices, 3, |w| w.iter().sum::<f32>() / w.len() as f32);
+        assert_eq!(smas, sma::run(prices, 3));
+    }
+
+    #[test]
+    fn test_rolling_max() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0];
+        assert_eq!(rolling(&prices, 2, |w| w.iter().cloned().fold(f32::MIN, f32::max)), vec![10.0, 11.0, 11.0, 6.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the rolling window. Received 2, but required 3.")]
+    fn test_rolling_not_enough_elements() {
+        rolling(&[5.0, 10.0], 3, |w| w[0]);
+    }
+
+    #[test]
+    fn test_rolling_median_outliers() {
+        let prices = vec![10.0, 11.0, 10.0, 500.0, 11.0, 10.0, 11.0];
+        let medians = rolling_median(&prices, 3);
+        assert_eq!(medians, vec![10.0, 11.0, 11.0, 11.0, 11.0]);
+        // The spike drags the SMA far away from the typical price
+        let smas = sma::run(prices.to_vec(), 3);
+        assert_eq!(smas[1] > 100.0, true);
+    }
+
+    #[test]
+    fn test_rolling_quantile_matches_naive() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0];
+        for &q in [0.0, 0.25, 0.9, 1.0].iter() {
+            let naive = rolling(&prices, 4, |w| {
+                let mut sorted = w.to_vec();
+                sorted.sort_by(|a, b| a.total_cmp(b));
+                return quantile(&sorted, q);
+            });
+            assert_eq!(rolling_quantile(&prices, 4, q), naive);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the rolling quantile. Received 2, but required 3.")]
+    fn test_rolling_median_not_enough_elements() {
+        rolling_median(&[5.0, 10.0], 3);
+    }
+}
@@ -1,3 +1,7 @@
+use crate::stock::ta::{align, ema, find_pivots, ht_period, stats, Alignment, Indicator, PivotKind, PriceSource, Rounding, Series};
+use crate::stock::ta::divergence::SWING_BARS;
+use crate::stock::ta::util::{self, InputPolicy, TaError};
+
 /// Calculate the relative strength index (RSI) on a `Vec<f32>` of price data.
 /// 
 /// ### Definition
@@ -17,7 +21,17 @@
 /// - `cl`: current loss (or 0 if a gain)
 /// 
 /// NOTE: The first calculation of the RSI is calculated by `rs1` above whereas the
-/// remaining RSIs are calculated using `rsi2`.
+/// remaining RSIs are calculated using `rsi2`. With exactly `period+1` prices
+/// only `rsi1` is emitted.
+///
+/// NOTE: When there are neither gains nor losses (e.g. `period` unchanged
+/// prices) the ratio is `0 / 0`, which by convention yields a neutral RSI
+/// of 50. Only gains yields 100 and only losses yields 0.
+///
+/// NOTE: An unchanged price is a gain and a loss of 0, so (as in Wilder's
+/// definition) it decays both averages. The RSI of the flat bar itself is
+/// unchanged, but the following bars weigh later changes more than an
+/// implementation that skips flat bars would.
 /// 
 /// ### Usage
 /// When increasing above 70, the RSI signifies that the security is overbought.
@@ -53,97 +67,901 @@
 /// 
 /// # Arguments
 /// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
 /// 
 /// ### Example
 /// ```
-/// rsi::run(prices);
-/// rsi::run(prices);
+/// rsi::run(prices, 14);
+/// rsi::run(prices, 9);
 /// ```
 /// 
 /// #### Resources
 /// - https://www.investopedia.com/terms/r/rsi.asp
-pub fn run(prices: Vec<f32>) -> Vec<f32> {
-    const PERIOD: usize = 14;
-    if prices.len() < PERIOD+1 { panic!("Not enough entries to calculate the RSI. Received {}, but required {}.", prices.len(), PERIOD+1); }
-
-    // AVG Gain/Loss
-    let mut ag: f32 = 0.0;
-    let mut al: f32 = 0.0;
-    let mut last_price: f32 = 0.0;
+pub fn run(prices: Vec<f32>, period: usize) -> Vec<f32> {
+    return run_iter(&prices, period).collect();
+}
+
+/// Calculate the RSI lazily, yielding each value as it is computed instead
+/// of collecting them into a `Vec` (which `run` does).
+///
+/// Useful in batch jobs that only iterate over the RSI once, e.g. to count
+/// overbought bars or to feed it straight into another computation.
+///
+/// NOTE: The input is validated up front, so an invalid `period` or too
+/// few/non-finite `prices` panic on the call rather than on iteration.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
+///
+/// ### Example
+/// ```
+/// rsi::run_iter(&prices, 14).filter(|&rsi| rsi > 70.0).count();
+/// ```
+pub fn run_iter(prices: &[f32], period: usize) -> impl Iterator<Item = f32> + '_ {
+    validate(prices, period);
+    let mut state = RsiState::new(period);
+    return prices.iter().filter_map(move |&p| state.update(p));
+}
+
+/// Panic on the preconditions shared by the RSI's entry points.
+fn validate(prices: &[f32], period: usize) {
+    if period == 0 { panic!("Invalid period for RSI. Received 0, but required at least 1."); }
+    match util::check_input(prices, period+1) {
+        Ok(_) => {},
+        Err(TaError::NotEnoughEntries { received, required }) => panic!("Not enough entries to calculate the RSI. Received {}, but required {}.", received, required),
+        Err(error) => panic!("{}", error),
+    };
+}
+
+/// Calculate the RSI after applying `policy` to zero or negative prices.
+///
+/// Unlike `run`, bad input is reported as an error rather than a panic:
+/// a zero period, too few prices, non-finite prices, or (with
+/// `InputPolicy::Strict`) a zero or negative price.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
+/// * `policy` - Treatment of zero or negative prices
+///
+/// ### Example
+/// ```
+/// rsi::run_with_policy(prices, 14, InputPolicy::Strict)?;
+/// ```
+pub fn run_with_policy(prices: Vec<f32>, period: usize, policy: InputPolicy) -> Result<Vec<f32>, TaError> {
+    if period == 0 { return Err(TaError::InvalidPeriod { received: period, required: 1 }); }
+    let prices = util::apply_policy(&prices, policy)?;
+    util::check_input(&prices, period+1)?;
+    return Ok(run(prices, period));
+}
+
+/// Calculate the RSI without ever panicking, for best-effort batch jobs.
+///
+/// - A zero `period` or fewer than `period+1` prices yield an empty `Vec`.
+/// - A non-finite price (`NaN` or infinite) is skipped: its bar is `NaN`
+///   and the next change is measured from the last finite price.
+/// - Bars before `period+1` finite prices have been seen are `NaN`.
+///
+/// Otherwise the output is aligned with, and identical to, `run`.
+///
+/// NOTE: This trades correctness signaling for robustness. Bad input is
+/// silently absorbed rather than reported, so prefer `run_with_policy`
+/// wherever bad data should stop the calculation.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
+///
+/// ### Example
+/// ```
+/// rsi::run_lenient(&prices, 14);
+/// ```
+pub fn run_lenient(prices: &[f32], period: usize) -> Vec<f32> {
     let mut rsis: Vec<f32> = Vec::new();
-    for i in 0..PERIOD+1 {
-        if i == 0 {
-            last_price = match prices.get(0) {
-                Some(&v) => v,
-                None => 0.0,
-            };
-            continue;
-        }
-        let current_price = match prices.get(i) {
-            Some(&v) => v,
-            None => 0.0,
+    if period == 0 || prices.len() <= period { return rsis; }
+    let mut state = RsiState::new(period);
+    for (i, &price) in prices.iter().enumerate() {
+        let rsi = if price.is_finite() { state.update(price) } else { None };
+        if i >= period { rsis.push(rsi.unwrap_or(f32::NAN)); }
+    }
+    return rsis;
+}
+
+/// Calculate a smoothed RSI (after Sylvain Vervoort): an EMA of the RSI
+/// that reduces its bar-to-bar noise.
+///
+/// An EMA of values between 0 and 100 stays between 0 and 100, so the usual
+/// 30/70 levels still apply, although the smoothed line reaches them less
+/// often.
+///
+/// NOTE: The EMA's SMA seed is not emitted, so the first value corresponds
+/// to `prices[rsi_period + smooth_period]`.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `rsi_period` - Number of price changes to average (commonly 14)
+/// * `smooth_period` - Number of periods of the EMA of the RSI
+///
+/// ### Example
+/// ```
+/// rsi::run_smoothed(&prices, 14, 5);
+/// ```
+pub fn run_smoothed(prices: &[f32], rsi_period: usize, smooth_period: usize) -> Vec<f32> {
+    let required = rsi_period + smooth_period + 1;
+    if prices.len() < required { panic!("Not enough entries to calculate the smoothed RSI. Received {}, but required {} (rsi_period+smooth_period+1).", prices.len(), required); }
+    return ema::run(run(prices.to_vec(), rsi_period), smooth_period);
+}
+
+/// Calculate the RSI and apply `rounding` to the emitted values.
+///
+/// Charting platforms differ in how they display the RSI (TradingView
+/// rounds to 2 decimals, others truncate or keep full precision), so
+/// matching a platform's output exactly needs its rounding mode.
+/// `Rounding::None` (the default) is the same as `run`.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
+/// * `rounding` - Rounding applied to every RSI value
+///
+/// ### Example
+/// ```
+/// rsi::run_with(prices, 14, Rounding::Round(2));
+/// ```
+pub fn run_with(prices: Vec<f32>, period: usize, rounding: Rounding) -> Vec<f32> {
+    let mut rsis = run(prices, period);
+    rounding.apply(&mut rsis);
+    return rsis;
+}
+
+/// Calculate the RSI, ignoring price changes smaller than `min_change`.
+///
+/// Tiny fluctuations (e.g. a one-tick move on a low-volatility instrument)
+/// still count as gains or losses in `run` and make the RSI noisy. Here any
+/// change with `|change| < min_change` is treated as flat: zero gain and
+/// zero loss.
+///
+/// NOTE: Flat bars still count towards the period, so they pull both the
+/// average gain and the average loss towards zero by `1 / period` of their
+/// value each. The ratio between the two (and so the RSI) only moves on
+/// changes outside the deadband.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
+/// * `min_change` - Smallest absolute price change counted as a gain or loss
+///
+/// ### Example
+/// ```
+/// rsi::run_deadband(prices, 14, 0.05);
+/// ```
+pub fn run_deadband(prices: Vec<f32>, period: usize, min_change: f32) -> Vec<f32> {
+    validate(&prices, period);
+    let mut state = RsiState::with_min_change(period, min_change);
+    return prices.iter().filter_map(|&p| state.update(p)).collect();
+}
+
+/// Calculate the RSI on a `Vec<i64>` of prices stored as integer cents.
+///
+/// Price changes are taken exactly in cents, and the seed gain/loss sums
+/// are exact integers. Only the averages (carried in `f64` through Wilder's
+/// smoothing) and the final ratio are floating point. This avoids the `f32`
+/// rounding that builds up in the gain/loss sums of `run`.
+///
+/// # Arguments
+/// * `prices` - `Vec<i64>` containing prices in cents for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
+///
+/// ### Example
+/// ```
+/// rsi::run_cents(vec![10137, 10142, 10129, ...], 14);
+/// ```
+pub fn run_cents(prices: Vec<i64>, period: usize) -> Vec<f32> {
+    if period == 0 { panic!("Invalid period for RSI. Received 0, but required at least 1."); }
+    if prices.len() < period+1 { panic!("Not enough entries to calculate the RSI. Received {}, but required {}.", prices.len(), period+1); }
+
+    // Seed with the exact sums of the first `period` changes
+    let mut gain_sum: i64 = 0;
+    let mut loss_sum: i64 = 0;
+    for i in 1..period+1 {
+        let change = prices[i] - prices[i-1];
+        if change > 0 { gain_sum += change; } else { loss_sum -= change; }
+    }
+    let p = period as f64;
+    let mut ag = gain_sum as f64 / p;
+    let mut al = loss_sum as f64 / p;
+
+    let mut rsis: Vec<f32> = vec![from_averages_f64(ag, al) as f32];
+    for i in period+1..prices.len() {
+        let change = prices[i] - prices[i-1];
+        let gain = if change > 0 { change } else { 0 };
+        let loss = if change < 0 { -change } else { 0 };
+        ag = ((ag * (p - 1.0)) + gain as f64) / p;
+        al = ((al * (p - 1.0)) + loss as f64) / p;
+        rsis.push(from_averages_f64(ag, al) as f32);
+    }
+    return rsis;
+}
+
+/// Calculate the RSI on `source` (e.g. the typical price or the weighted
+/// close) of a `&[(f32, f32, f32)]` of price data instead of the close.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `period` - Number of price changes to average (commonly 14)
+/// * `source` - Price of each bar the RSI is calculated on
+///
+/// ### Example
+/// ```
+/// rsi::run_on(&prices, 14, PriceSource::Typical);
+/// ```
+pub fn run_on(prices: &[(f32, f32, f32)], period: usize, source: PriceSource) -> Vec<f32> {
+    return run(prices.iter().map(|&bar| source.price(bar)).collect(), period);
+}
+
+/// Calculate the RSI of a `Vec<u64>` of volume data, applying the RSI
+/// formula to volume changes instead of price changes.
+///
+/// ### Usage
+/// Surfaces volume momentum: a reading above 70 shows participation
+/// building up, which lends weight to a move in price, while a price move
+/// on a falling volume RSI is suspect.
+///
+/// NOTE: Volumes are projected to `f32`, which is exact up to 2^24 (about
+/// 16.7 million). Above that, very small changes are rounded away.
+///
+/// # Arguments
+/// * `volumes` - `Vec<u64>` containing volume data for a period of time
+/// * `period` - Number of volume changes to average (commonly 14)
+///
+/// ### Example
+/// ```
+/// ta::volume_rsi(volumes, 14);
+/// ```
+pub fn volume_rsi(volumes: Vec<u64>, period: usize) -> Vec<f32> {
+    return run(volumes.iter().map(|&v| v as f32).collect(), period);
+}
+
+/// RSI from an average gain and loss, with the flat case `0 / 0` as 50.
+fn from_averages(ag: f32, al: f32) -> f32 {
+    if ag == 0.0 && al == 0.0 { return 50.0; }
+    return 100.0 - (100.0 / (1.0 + (ag / al)));
+}
+
+/// `from_averages` in `f64`, so `run_cents` only rounds the final RSI.
+fn from_averages_f64(ag: f64, al: f64) -> f64 {
+    if ag == 0.0 && al == 0.0 { return 50.0; }
+    return 100.0 - (100.0 / (1.0 + (ag / al)));
+}
+
+/// Shortest period `run_adaptive` will use.
+pub const ADAPTIVE_MIN_PERIOD: usize = 5;
+/// Longest period `run_adaptive` will use.
+pub const ADAPTIVE_MAX_PERIOD: usize = 25;
+
+/// Calculate an adaptive RSI whose period follows the market's dominant
+/// cycle (see `ht_period::dominant_cycle`) bar by bar.
+///
+/// The period on each bar is half of the dominant cycle, rounded and
+/// clamped to `[ADAPTIVE_MIN_PERIOD, ADAPTIVE_MAX_PERIOD]` (5 to 25) so
+/// that a noisy cycle estimate cannot make the RSI jump around or flatten
+/// out. The average gain/loss are seeded with a simple average of the
+/// first period's changes, then updated with Wilder's smoothing using the
+/// period of the current bar.
+///
+/// NOTE: The first value corresponds to `prices[ht_period::WARMUP]`
+/// (`prices[32]`), the first bar with a cycle estimate.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+///
+/// ### Example
+/// ```
+/// rsi::run_adaptive(prices);
+/// ```
+pub fn run_adaptive(prices: Vec<f32>) -> Vec<f32> {
+    let cycles = ht_period::dominant_cycle(prices.to_vec());
+    let first = ht_period::WARMUP;
+    let period_at = |k: usize| -> usize {
+        return ((cycles[k] / 2.0).round() as usize).clamp(ADAPTIVE_MIN_PERIOD, ADAPTIVE_MAX_PERIOD);
+    };
+
+    // Seed with the simple average of the changes leading up to the first bar
+    let seed = period_at(0);
+    let mut ag = 0.0;
+    let mut al = 0.0;
+    for i in first+1-seed..first+1 {
+        let change = prices[i] - prices[i-1];
+        if change > 0.0 { ag += change; } else { al -= change; }
+    }
+    ag /= seed as f32;
+    al /= seed as f32;
+
+    let mut rsis: Vec<f32> = vec![from_averages(ag, al)];
+    for k in 1..cycles.len() {
+        let period = period_at(k) as f32;
+        let i = first + k;
+        let change = prices[i] - prices[i-1];
+        let gain = if change > 0.0 { change } else { 0.0 };
+        let loss = if change < 0.0 { -change } else { 0.0 };
+        ag = ((ag * (period - 1.0)) + gain) / period;
+        al = ((al * (period - 1.0)) + loss) / period;
+        rsis.push(from_averages(ag, al));
+    }
+    return rsis;
+}
+
+/// Calculate the RSI with a chosen output `Alignment`.
+///
+/// With `Alignment::PaddedFront` the output is as long as `prices`, with
+/// `None` for the first `period` bars (the RSI needs `period` price changes).
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
+/// * `alignment` - Alignment of the RSI values to `prices`
+///
+/// ### Example
+/// ```
+/// rsi::run_aligned(prices, 14, Alignment::PaddedFront);
+/// ```
+pub fn run_aligned(prices: Vec<f32>, period: usize, alignment: Alignment) -> Vec<Option<f32>> {
+    let len = prices.len();
+    return align(run(prices, period), len, alignment);
+}
+
+/// Calculate the RSI as a `Series`, whose `start_index` is `period` (the
+/// RSI needs `period` price changes).
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `period` - Number of price changes to average (commonly 14)
+///
+/// ### Example
+/// ```
+/// let series = rsi::run_series(prices, 14);
+/// series.value_at(i);
+/// ```
+pub fn run_series(prices: Vec<f32>, period: usize) -> Series {
+    return Series { values: run(prices, period), start_index: period };
+}
+
+/// Suggest oversold/overbought levels from the observed distribution of a
+/// `&[f32]` of RSI values rather than the fixed 30/70 bands.
+///
+/// Low-volatility instruments may rarely reach 30 or 70 while volatile ones
+/// spend long stretches beyond them, so the empirical `percentile` and
+/// `100 - percentile` percentiles of the RSI's own history are returned.
+///
+/// # Arguments
+/// * `rsis` - `&[f32]` containing RSI values (e.g. from `run`)
+/// * `percentile` - Percentile (0-100) for the oversold level, e.g. `10.0` for the 10th/90th
+///
+/// # Returns
+/// `(f32, f32)` in the form of `(oversold, overbought)`.
+///
+/// ### Example
+/// ```
+/// let (oversold, overbought) = rsi::suggest_thresholds(&rsi::run(prices, 14), 10.0);
+/// ```
+pub fn suggest_thresholds(rsis: &[f32], percentile: f32) -> (f32, f32) {
+    if rsis.is_empty() { panic!("Not enough entries to suggest RSI thresholds. Received 0, but required 1."); }
+    if !(0.0..=100.0).contains(&percentile) { panic!("Invalid percentile. Received {}, but required a value between 0 and 100.", percentile); }
+    let mut sorted = rsis.to_vec();
+    sorted.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+    let low = stats::quantile(&sorted, percentile / 100.0);
+    let high = stats::quantile(&sorted, (100.0 - percentile) / 100.0);
+    return (low.min(high), low.max(high));
+}
+
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum FailureSwingKind {
+    /// A peak fails to exceed the prior peak.
+    Top,
+    /// A trough fails to undercut the prior trough.
+    Bottom,
+}
+
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub struct FailureSwing {
+    /// Index of the prior peak/trough.
+    pub first: usize,
+    /// Index of the peak/trough that failed.
+    pub second: usize,
+    pub kind: FailureSwingKind,
+}
+
+/// Detect failure swings in a `&[f32]` of RSI values, automating the
+/// "fails to reach 70 multiple times" pattern of `run`.
+///
+/// ### Definition
+/// Peaks and troughs are swing highs/lows of the RSI at least `SWING_BARS`
+/// bars wide (see `find_pivots`). A top failure is a peak below the prior
+/// peak, and a bottom failure a trough above the prior trough, as long as
+/// the two are at most `window` bars apart.
+///
+/// NOTE: A swing is only confirmed `SWING_BARS` bars after it forms. Only
+/// the RSI's shape is compared, so filter on the levels (e.g. a first peak
+/// above 70) where a classic failure swing is wanted.
+///
+/// # Arguments
+/// * `rsis` - `&[f32]` containing RSI values (e.g. from `run`)
+/// * `window` - Largest number of bars between the two peaks/troughs
+///
+/// ### Example
+/// ```
+/// rsi::failure_swings(&rsi::run(prices, 14), 20);
+/// ```
+pub fn failure_swings(rsis: &[f32], window: usize) -> Vec<FailureSwing> {
+    let mut swings: Vec<FailureSwing> = Vec::new();
+    let mut last_peak: Option<usize> = None;
+    let mut last_trough: Option<usize> = None;
+    for pivot in find_pivots(rsis, SWING_BARS) {
+        let i = pivot.index;
+        match pivot.kind {
+            PivotKind::High => {
+                if let Some(prev) = last_peak {
+                    if i - prev <= window && rsis[i] < rsis[prev] {
+                        swings.push(FailureSwing { first: prev, second: i, kind: FailureSwingKind::Top });
+                    }
+                }
+                last_peak = Some(i);
+            },
+            PivotKind::Low => {
+                if let Some(prev) = last_trough {
+                    if i - prev <= window && rsis[i] > rsis[prev] {
+                        swings.push(FailureSwing { first: prev, second: i, kind: FailureSwingKind::Bottom });
+                    }
+                }
+                last_trough = Some(i);
+            },
         };
-        if current_price > last_price {
-            ag += current_price - last_price;
-            // al += 0.0;
-        } else if current_price < last_price {
-            // ag += 0.0;
-            al += last_price - current_price;
-        }
-        last_price = current_price;
-    }
-    ag = ag / PERIOD as f32;
-    al = al / PERIOD as f32;
-    let rs = ag / al;
-    let rsi_1 = 100.0 - (100.0 / (1.0 + rs));
-    rsis.push(rsi_1);
-
-    // Find remaining RSIs
-    for i in PERIOD+1..prices.len() {
-        let current_price = match prices.get(i) {
-            Some(&v) => v,
-            None => 0.0,
+    }
+    return swings;
+}
+
+/// Incremental RSI fed one price at a time (e.g. from a live feed), giving
+/// the same values as `run` without keeping the price history.
+///
+/// ### Example
+/// ```
+/// let mut state = rsi::RsiState::new(14);
+/// for price in prices {
+///     if let Some(rsi) = state.update(price) { ... }
+/// }
+/// ```
+pub struct RsiState {
+    period: usize,
+    min_change: f32,
+    price_prev: Option<f32>,
+    seen: usize,
+    gain_sum: f64,
+    loss_sum: f64,
+    averages: Option<(f32, f32)>,
+}
+
+impl RsiState {
+    pub fn new(period: usize) -> Self {
+        return Self::with_min_change(period, 0.0);
+    }
+
+    /// Like `new`, but any change with `|change| < min_change` is treated
+    /// as flat (see `run_deadband`).
+    pub fn with_min_change(period: usize, min_change: f32) -> Self {
+        if period == 0 { panic!("Invalid period for RSI. Received 0, but required at least 1."); }
+        return Self {
+            period: period,
+            min_change: min_change,
+            price_prev: None,
+            seen: 0,
+            gain_sum: 0.0,
+            loss_sum: 0.0,
+            averages: None,
         };
-        if current_price > last_price {
-            ag = ((ag * (PERIOD as f32-1.0)) + (current_price - last_price)) / PERIOD as f32;
-            al = ((al * (PERIOD as f32-1.0))) / PERIOD as f32;
-        } else if current_price < last_price {
-            ag = ((ag * (PERIOD as f32-1.0))) / PERIOD as f32;
-            al = ((al * (PERIOD as f32-1.0)) + (last_price - current_price)) / PERIOD as f32;
-        }
-        let rs = ag / al;
-        let rsi = 100.0 - (100.0 / (1.0 + rs));
-        rsis.push(rsi);
-        last_price = current_price;
     }
-    return rsis;
+
+    /// Feed the next price, returning the RSI once `period+1` prices have
+    /// been seen.
+    pub fn update(&mut self, price: f32) -> Option<f32> {
+        let price_prev = match self.price_prev {
+            Some(v) => v,
+            None => {
+                self.price_prev = Some(price);
+                return None;
+            },
+        };
+        self.price_prev = Some(price);
+        let mut change = price - price_prev;
+        if change.abs() < self.min_change { change = 0.0; }
+        let gain = if change > 0.0 { change } else { 0.0 };
+        let loss = if change < 0.0 { -change } else { 0.0 };
+        let p = self.period as f32;
+        let (ag, al) = match self.averages {
+            Some((ag_prev, al_prev)) => (((ag_prev * (p - 1.0)) + gain) / p, ((al_prev * (p - 1.0)) + loss) / p),
+            None => {
+                // Seed with the simple average of the first `period` changes
+                self.gain_sum += gain as f64;
+                self.loss_sum += loss as f64;
+                self.seen += 1;
+                if self.seen < self.period { return None; }
+                ((self.gain_sum as f32) / p, (self.loss_sum as f32) / p)
+            },
+        };
+        self.averages = Some((ag, al));
+        return Some(from_averages(ag, al));
+    }
+}
+
+/// `Indicator` implementation of the relative strength index's `run`.
+pub struct Rsi {
+    pub period: usize,
+}
+
+impl Indicator for Rsi {
+    type Input = Vec<f32>;
+    type Output = Vec<f32>;
+
+    fn compute(&self, data: &Self::Input) -> Self::Output { return run(data.to_vec(), self.period); }
+    fn warmup(&self) -> usize { return self.period; }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stock::ta::delta;
+    use crate::stock::testutil::{assert_close, geometric_brownian};
+
+    const EPSILON: f32 = 1e-4;
 
     #[test]
     fn test_run_simple() {
         let prices = vec![10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0];
-        assert_eq!(run(prices), vec![57.69231]);
+        assert_close(&run(prices, 14), &[57.69231], EPSILON);
     }
 
     #[test]
     fn test_run_complex() {
         let prices = vec![10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0];
-        assert_eq!(run(prices), vec![57.69231, 49.492382]);
+        assert_close(&run(prices, 14), &[57.69231, 49.492382], EPSILON);
     }
 
     #[test]
     fn test_run_random() {
         let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
-        assert_eq!(run(prices), vec![59.210526, 48.267326, 49.52316, 51.120464, 51.451355, 49.641834, 49.268627, 60.9628, 57.491276, 47.199604]);
+        assert_close(&run(prices, 14), &[59.210526, 48.267326, 49.52316, 51.120464, 51.451355, 49.641834, 49.268627, 60.9628, 57.491276, 47.199604], EPSILON);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid entry at index 2. Received NaN, but required a finite value.")]
+    fn test_run_nan() {
+        run(vec![10.0, 12.0, f32::NAN, 13.0], 3);
+    }
+
+    #[test]
+    fn test_run_with_rounding() {
+        let prices = vec![10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0];
+        // Full precision: [57.69231, 49.492382]
+        assert_eq!(run_with(prices.to_vec(), 14, Rounding::None), run(prices.to_vec(), 14));
+        assert_eq!(run_with(prices.to_vec(), 14, Rounding::default()), run(prices.to_vec(), 14));
+        assert_eq!(run_with(prices.to_vec(), 14, Rounding::Round(2)), vec![57.69, 49.49]);
+        assert_eq!(run_with(prices.to_vec(), 14, Rounding::Round(1)), vec![57.7, 49.5]);
+        assert_eq!(run_with(prices.to_vec(), 14, Rounding::Truncate(1)), vec![57.6, 49.4]);
+        assert_eq!(run_with(prices, 14, Rounding::Truncate(0)), vec![57.0, 49.0]);
+    }
+
+    #[test]
+    fn test_run_with_policy_negative_price() {
+        let prices = vec![10.0, 12.0, -11.0, 13.0];
+        assert_eq!(run_with_policy(prices.to_vec(), 3, InputPolicy::Strict), Err(TaError::NonPositive { index: 2, value: -11.0 }));
+        assert_eq!(run_with_policy(prices, 3, InputPolicy::Lenient), Ok(run(vec![10.0, 12.0, util::MIN_PRICE, 13.0], 3)));
+    }
+
+    #[test]
+    fn test_run_with_policy_not_enough_elements() {
+        assert_eq!(run_with_policy(vec![10.0, 12.0], 3, InputPolicy::Strict), Err(TaError::NotEnoughEntries { received: 2, required: 4 }));
+    }
+
+    #[test]
+    fn test_run_iter_matches_run() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let lazy: Vec<f32> = run_iter(&prices, 14).collect();
+        assert_eq!(lazy, run(prices.to_vec(), 14));
+        assert_eq!(lazy, run_deadband(prices.to_vec(), 14, 0.0));
+        assert_close(&lazy, &[59.210526, 48.267326, 49.52316, 51.120464, 51.451355, 49.641834, 49.268627, 60.9628, 57.491276, 47.199604], EPSILON);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the RSI. Received 2, but required 15.")]
+    fn test_run_iter_not_enough_elements() {
+        run_iter(&[10.0, 12.0], 14).count();
+    }
+
+    #[test]
+    fn test_run_deadband_zero() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        assert_eq!(run_deadband(prices.to_vec(), 14, 0.0), run(prices, 14));
+    }
+
+    #[test]
+    fn test_run_deadband_smoother() {
+        // A low-volatility instrument: one-tick noise with a real move every 5 bars
+        let mut prices: Vec<f32> = vec![100.0];
+        for i in 1..80 {
+            let last = prices[i-1];
+            if i % 5 == 0 {
+                prices.push(last + if i % 20 < 10 { 1.0 } else { -0.8 });
+            } else {
+                prices.push(last + if i % 2 == 0 { 0.01 } else { -0.01 });
+            }
+        }
+        let roughness = |rsis: &[f32]| -> f32 {
+            return rsis.windows(2).map(|w| (w[1] - w[0]).abs()).sum();
+        };
+        let plain = run(prices.to_vec(), 14);
+        let filtered = run_deadband(prices, 14, 0.1);
+        assert_eq!(plain.len(), filtered.len());
+        assert_eq!(roughness(&filtered) < roughness(&plain), true);
+    }
+
+    #[test]
+    fn test_run_smoothed_less_noisy() {
+        let prices = geometric_brownian(11, 200, 0.0, 0.02, 100.0);
+        let raw = run(prices.to_vec(), 14);
+        let smoothed = run_smoothed(&prices, 14, 5);
+        assert_eq!(smoothed.len(), prices.len() - 14 - 5);
+        assert_eq!(smoothed.iter().all(|&v| (0.0..=100.0).contains(&v)), true);
+        let raw_noise = stats::std_dev(&delta(&raw[5..]));
+        let smoothed_noise = stats::std_dev(&delta(&smoothed));
+        assert_eq!(smoothed_noise < raw_noise, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the smoothed RSI. Received 19, but required 20 (rsi_period+smooth_period+1).")]
+    fn test_run_smoothed_not_enough_elements() {
+        run_smoothed(&[10.0; 19], 14, 5);
+    }
+
+    #[test]
+    fn test_run_flat_bar_decays_averages() {
+        // After the flat bar: ag = 0.5, al = 0.25, then +1 gives ag = 0.75, al = 0.125.
+        // Skipping the flat bar would give ag = 1, al = 0.25 and an RSI of 80.
+        assert_eq!(run(vec![10.0, 12.0, 11.0, 11.0, 12.0], 2), vec![66.66667, 66.66667, 85.71429]);
+    }
+
+    #[test]
+    fn test_run_lenient_matches_run() {
+        let prices = vec![10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 12.0, 15.0, 13.0, 18.0, 10.0, 11.0];
+        assert_eq!(run_lenient(&prices, 14), run(prices.to_vec(), 14));
+    }
+
+    #[test]
+    fn test_run_lenient_short() {
+        assert_eq!(run_lenient(&[], 14), Vec::<f32>::new());
+        assert_eq!(run_lenient(&[10.0, 12.0, 15.0], 14), Vec::<f32>::new());
+        assert_eq!(run_lenient(&[10.0, 12.0, 15.0], 0), Vec::<f32>::new());
+    }
+
+    #[test]
+    fn test_run_lenient_non_finite() {
+        let prices = vec![10.0, 12.0, f32::NAN, 11.0, 13.0, f32::INFINITY, 12.0];
+        let rsis = run_lenient(&prices, 2);
+        assert_eq!(rsis.len(), prices.len() - 2);
+        // Changes: +2, (skipped), -1, +2, (skipped), -1
+        assert_eq!(rsis[0].is_nan(), true);
+        assert_eq!(rsis[1], 66.66667);
+        assert_eq!(rsis[2] > rsis[1], true);
+        assert_eq!(rsis[3].is_nan(), true);
+        assert_eq!(rsis[4] < rsis[2], true);
+        assert_eq!(run_lenient(&[f32::NAN; 5], 2).iter().all(|r| r.is_nan()), true);
+    }
+
+    #[test]
+    fn test_failure_swings_top() {
+        // Overbought peak, a pullback, then a rally that stalls below it
+        let rsis = vec![60.0, 68.0, 76.0, 69.0, 62.0, 58.0, 63.0, 67.0, 64.0, 55.0, 45.0];
+        assert_eq!(failure_swings(&rsis, 10), vec![
+            FailureSwing { first: 2, second: 7, kind: FailureSwingKind::Top },
+        ]);
+        // The peaks are too far apart
+        assert_eq!(failure_swings(&rsis, 4), Vec::new());
+    }
+
+    #[test]
+    fn test_failure_swings_bottom() {
+        let rsis = vec![40.0, 32.0, 24.0, 31.0, 38.0, 42.0, 36.0, 29.0, 33.0, 45.0, 55.0];
+        assert_eq!(failure_swings(&rsis, 10), vec![
+            FailureSwing { first: 2, second: 7, kind: FailureSwingKind::Bottom },
+        ]);
+    }
+
+    #[test]
+    fn test_failure_swings_new_high() {
+        let rsis = vec![60.0, 68.0, 72.0, 69.0, 62.0, 58.0, 66.0, 78.0, 70.0, 55.0, 45.0];
+        assert_eq!(failure_swings(&rsis, 10), Vec::new());
+    }
+
+    #[test]
+    fn test_run_on_source() {
+        let prices = vec![
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+        ];
+        let closes: Vec<f32> = prices.iter().map(|p| p.0).collect();
+        let typicals: Vec<f32> = prices.iter().map(|&(c, l, h)| (h + l + c) / 3.0).collect();
+        let by_close = run_on(&prices, 14, PriceSource::Close);
+        let by_typical = run_on(&prices, 14, PriceSource::Typical);
+        assert_eq!(by_close, run(closes, 14));
+        assert_eq!(by_typical, run(typicals, 14));
+        assert_eq!(by_close.len(), by_typical.len());
+        assert_ne!(by_close, by_typical);
+    }
+
+    #[test]
+    fn test_volume_rsi_rising() {
+        let volumes: Vec<u64> = vec![
+            1000, 1200, 1100, 1500, 1400, 1800, 2100, 2000,
+            2600, 3000, 2900, 3500, 4000, 4600, 4400, 5200,
+        ];
+        let rsis = volume_rsi(volumes.to_vec(), 14);
+        assert_eq!(rsis.len(), 2);
+        assert_eq!(rsis.iter().all(|&r| r > 70.0), true);
+        assert_eq!(rsis, run(volumes.iter().map(|&v| v as f32).collect(), 14));
+    }
+
+    #[test]
+    fn test_run_cents_matches_float() {
+        let dollars: Vec<f32> = vec![
+            101.37, 101.42, 101.29, 101.55, 101.61, 101.48, 101.80, 101.77,
+            101.93, 101.60, 101.52, 101.74, 101.89, 102.03, 101.98, 102.11,
+            101.85, 101.70, 101.92, 102.20, 102.35, 102.18, 102.01, 102.40,
+        ];
+        let cents: Vec<i64> = dollars.iter().map(|d| (d * 100.0).round() as i64).collect();
+        assert_close(&run_cents(cents, 14), &run(dollars, 14), 1e-2);
+    }
+
+    #[test]
+    fn test_run_cents_simple() {
+        // Same as `test_run_custom_period` in cents
+        assert_eq!(run_cents(vec![1000, 1200, 1100, 1300], 3), vec![80.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the RSI. Received 2, but required 4.")]
+    fn test_run_cents_not_enough_elements() {
+        run_cents(vec![1000, 1200], 3);
+    }
+
+    #[test]
+    fn test_rsi_state_matches_run() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let mut state = RsiState::new(14);
+        let streamed: Vec<f32> = prices.iter().filter_map(|&p| state.update(p)).collect();
+        assert_eq!(streamed, run(prices, 14));
+    }
+
+    #[test]
+    fn test_run_adaptive() {
+        // The `test_run_random` pattern, repeated to cover the cycle warm-up
+        let pattern = [5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let prices: Vec<f32> = pattern.iter().cycle().take(pattern.len() * 8).copied().collect();
+        let rsis = run_adaptive(prices.to_vec());
+        assert_eq!(rsis.len(), prices.len() - ht_period::WARMUP);
+        assert_eq!(rsis.iter().all(|&r| (0.0..=100.0).contains(&r)), true);
+    }
+
+    #[test]
+    fn test_run_adaptive_uptrend() {
+        let prices: Vec<f32> = (0..60).map(|i| 100.0 + i as f32 + if i % 2 == 0 { 0.0 } else { -0.5 }).collect();
+        let rsis = run_adaptive(prices);
+        assert_eq!(rsis.iter().all(|&r| r > 50.0 && r <= 100.0), true);
+    }
+
+    #[test]
+    fn test_run_aligned_trimmed() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let aligned = run_aligned(prices.to_vec(), 14, Alignment::Trimmed);
+        assert_eq!(aligned.len(), 10);
+        assert_eq!(aligned, run(prices, 14).into_iter().map(Some).collect::<Vec<Option<f32>>>());
+    }
+
+    #[test]
+    fn test_run_aligned_padded_front() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let rsis = run(prices.to_vec(), 14);
+        let aligned = run_aligned(prices.to_vec(), 14, Alignment::PaddedFront);
+        assert_eq!(aligned.len(), prices.len());
+        assert_eq!(aligned[..14].iter().all(|v| v.is_none()), true);
+        assert_eq!(aligned[14], Some(rsis[0]));
+        assert_eq!(aligned[23], Some(rsis[9]));
+    }
+
+    #[test]
+    fn test_run_series() {
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        let series = run_series(prices.to_vec(), 14);
+        assert_eq!(series.start_index, 14);
+        assert_eq!(series.values, run(prices.to_vec(), 14));
+        assert_eq!(series.start_index + series.values.len(), prices.len());
     }
 
     #[test]
     #[should_panic(expected = "Not enough entries to calculate the RSI. Received 0, but required 15.")]
     fn test_run_not_enough_elements() {
-        run(vec![]);
+        run(vec![], 14);
+    }
+
+    #[test]
+    fn test_run_exact_length() {
+        // `period+1` prices yield a single RSI
+        assert_close(&run(vec![10.0, 12.0, 11.0, 13.0], 3), &[80.0], EPSILON);
+        assert_eq!(run(vec![10.0, 11.0, 12.0, 13.0], 3), vec![100.0]);
+        assert_eq!(run(vec![13.0, 12.0, 11.0, 10.0], 3), vec![0.0]);
+    }
+
+    #[test]
+    fn test_run_flat_prices() {
+        assert_eq!(run(vec![10.0; 15], 14), vec![50.0]);
+        assert_eq!(run(vec![10.0; 18], 14), vec![50.0; 4]);
+        assert_eq!(run_cents(vec![1000; 15], 14), vec![50.0]);
+        let mut state = RsiState::new(3);
+        let streamed: Vec<f32> = [10.0; 5].iter().filter_map(|&p| state.update(p)).collect();
+        assert_eq!(streamed, vec![50.0, 50.0]);
+    }
+
+    #[test]
+    fn test_run_custom_period() {
+        let prices = vec![10.0, 12.0, 11.0, 13.0];
+        // ag = 4 / 3 and al = 1 / 3
+        assert_close(&run(prices, 3), &[80.0], EPSILON);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid period for RSI. Received 0, but required at least 1.")]
+    fn test_run_zero_period() {
+        run(vec![10.0, 12.0], 0);
+    }
+
+    #[test]
+    fn test_suggest_thresholds_skewed() {
+        // Mostly overbought readings with a short tail into the 30s
+        let mut rsis: Vec<f32> = Vec::new();
+        for i in 0..80 { rsis.push(60.0 + (i % 20) as f32); }
+        for i in 0..20 { rsis.push(30.0 + i as f32); }
+        let (oversold, overbought) = suggest_thresholds(&rsis, 10.0);
+        assert_eq!(oversold < overbought, true);
+
+        // oversold = 39.9 and overbought = 77, the four 77s sit on the band
+        let below = rsis.iter().filter(|r| **r < oversold).count();
+        let above = rsis.iter().filter(|r| **r > overbought).count();
+        assert_eq!(below, 10);
+        assert_eq!(above, 8);
+
+        // Both bands are pulled up compared to the fixed 30/70 levels
+        assert_eq!(oversold > 30.0, true);
+        assert_eq!(overbought > 70.0, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid percentile. Received 120, but required a value between 0 and 100.")]
+    fn test_suggest_thresholds_invalid_percentile() {
+        suggest_thresholds(&[50.0], 120.0);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_run_with_policy_zero_period() {
+        let result = run_with_policy(vec![10.0, 12.0, 11.0, 13.0], 0, InputPolicy::Strict);
+        assert_eq!(result, Err(TaError::InvalidPeriod { received: 0, required: 1 }));
+        assert_eq!(result.unwrap_err().to_string(), "Invalid period. Received 0, but required at least 1.");
+    }
+}
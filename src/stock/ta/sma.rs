@@ -1,3 +1,5 @@
+use crate::stock::ta::util::RollingSum;
+
 /// Calculate the simple moving average (SMA) based on a `Vec<f32>` of price data.
 /// 
 /// ### Definition
@@ -15,6 +17,10 @@
 /// - death cross: 50-day SMA crosses below 200-day SMA
 /// - golden cross: 200-day SMA crosses below 50-day SMA
 /// 
+/// NOTE: The window sum is kept in `f64` and updated in O(1) per price (see
+/// `util::RollingSum`). Values are the `f64` window sum rounded to `f32`, so
+/// they can differ in the last bits from summing each window in `f32`.
+/// 
 /// # Arguments
 /// * `prices` - `Vec<f32>` containing prices for a period of time
 /// * `periods` - Number of periods to average
@@ -30,19 +36,46 @@
 pub fn run(prices: Vec<f32>, periods: usize) -> Vec<f32> {
     if prices.len() < periods { panic!("Not enough entries to calculate the SMA. Received {}, but required {}.", prices.len(), periods); }
     let mut smas: Vec<f32> = Vec::new();
-    let mut i = periods;
-    while {
-        let sum = prices[i-periods..i].iter().sum::<f32>();
-        smas.push(sum / periods as f32);
-        i += 1;
-        i <= prices.len()
-    } {}
+    let mut sum = RollingSum::new(periods);
+    for &p in prices.iter() {
+        sum.push(p);
+        if sum.is_full() { smas.push(sum.sum() / periods as f32); }
+    }
+    return smas;
+}
+
+/// Calculate the SMA, emitting approximate values before a full window is
+/// available once at least `min_periods` prices have been seen.
+///
+/// Partial windows average over whatever prices are present, like pandas'
+/// `rolling(periods, min_periods=...)`. The first value corresponds to
+/// `prices[min_periods - 1]`, and from `prices[periods - 1]` onwards the
+/// values are identical to `run`.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `periods` - Number of periods to average
+/// * `min_periods` - Number of prices required before emitting a value (`1..=periods`)
+///
+/// ### Example
+/// ```
+/// sma::run_min_periods(prices, 200, 20);
+/// ```
+pub fn run_min_periods(prices: Vec<f32>, periods: usize, min_periods: usize) -> Vec<f32> {
+    if min_periods == 0 || min_periods > periods { panic!("Invalid minimum periods. Received {}, but required a value between 1 and {}.", min_periods, periods); }
+    if prices.len() < min_periods { panic!("Not enough entries to calculate the SMA. Received {}, but required {}.", prices.len(), min_periods); }
+    let mut smas: Vec<f32> = Vec::new();
+    for i in min_periods..prices.len()+1 {
+        let window = &prices[i.saturating_sub(periods)..i];
+        smas.push(window.iter().sum::<f32>() / window.len() as f32);
+    }
     return smas;
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stock::testutil::geometric_brownian;
 
     #[test]
     fn test_run_simple() {
@@ -61,4 +94,33 @@ mod tests {
     fn test_run_not_enough_elements() {
         run(vec![10.0], 5);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_run_matches_naive() {
+        let prices = geometric_brownian(42, 1000, 0.0005, 0.01, 100.0);
+        // Each window summed from scratch in `f64`
+        let naive = |prices: &[f32], periods: usize| -> Vec<f32> {
+            return prices.windows(periods).map(|w| (w.iter().map(|&p| p as f64).sum::<f64>() as f32) / periods as f32).collect();
+        };
+        assert_eq!(run(prices.to_vec(), 20), naive(&prices, 20));
+
+        let prices = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0];
+        assert_eq!(run(prices.to_vec(), 3), naive(&prices, 3));
+    }
+
+    #[test]
+    fn test_run_min_periods() {
+        let prices = vec![10.0, 10.0, 15.0, 20.0, 20.0, 10.0, 10.0, 10.0];
+        let partial = run_min_periods(prices.to_vec(), 5, 2);
+        // Partial windows start 3 bars earlier than the full-window SMA
+        assert_eq!(partial, vec![10.0, 11.666667, 13.75, 15.0, 15.0, 15.0, 14.0]);
+        assert_eq!(partial[3..].to_vec(), run(prices.to_vec(), 5));
+        assert_eq!(run_min_periods(prices.to_vec(), 5, 5), run(prices, 5));
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid minimum periods. Received 6, but required a value between 1 and 5.")]
+    fn test_run_min_periods_invalid() {
+        run_min_periods(vec![10.0], 5, 6);
+    }
+}
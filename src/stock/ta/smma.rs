@@ -0,0 +1,73 @@
+use crate::stock::ta::sma;
+
+/// Calculate the smoothed moving average (SMMA) of a `Vec<f32>` of price data.
+///
+/// ### Definition
+/// Also known as Wilder's smoothing or the running moving average (RMA).
+/// Similar to the exponential moving average (EMA), but with a smoothing
+/// coefficient of `1 / p` rather than `2 / (1 + p)`, which makes it react
+/// more slowly to recent prices. It is the basis of the RSI, ATR, and ADX.
+///
+/// ### Formula
+/// `smma = ((smma_prev * (p - 1)) + v) / p`
+///
+/// - `v`: value (latest period)
+/// - `smma_prev`: previous period's smma
+/// - `p`: number of periods being averaged
+///
+/// NOTE: The first reading of the SMMA uses the SMA of the first `p` periods.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `periods` - Number of periods to average
+///
+/// ### Example
+/// ```
+/// smma::run(prices, 14);
+/// smma::run(prices, 20);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/m/movingaverage.asp
+pub fn run(prices: Vec<f32>, periods: usize) -> Vec<f32> {
+    if prices.len() < periods { panic!("Not enough entries to calculate the SMMA. Received {}, but required {}.", prices.len(), periods); }
+    let mut smmas: Vec<f32> = Vec::new();
+    // Use the SMA as its first `smma_prev`
+    let mut smma_prev = match sma::run(prices[0..periods].to_vec(), periods).pop() {
+        Some(v) => v,
+        None => 0.0,
+    };
+    smmas.push(smma_prev);
+    for i in periods..prices.len() {
+        let smma = ((smma_prev * (periods as f32 - 1.0)) + prices[i]) / periods as f32;
+        smma_prev = smma;
+        smmas.push(smma);
+    }
+    return smmas;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        let prices = vec![10.0, 12.0, 14.0];
+        assert_eq!(run(prices, 3), vec![12.0]);
+    }
+
+    #[test]
+    fn test_run_complex() {
+        // 12.0
+        // ((12.0 * 2) + 16) / 3 = 13.333333
+        // ((13.333333 * 2) + 18) / 3 = 14.888888
+        let prices = vec![10.0, 12.0, 14.0, 16.0, 18.0];
+        assert_eq!(run(prices, 3), vec![12.0, 13.333333, 14.888888]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the SMMA. Received 1, but required 5.")]
+    fn test_run_not_enough_elements() {
+        run(vec![10.0], 5);
+    }
+}
@@ -0,0 +1,157 @@
+use crate::stock::ta::{ema, sma, smma, wma};
+
+/// Moving average used to smooth a series of indicator values.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Smoothing {
+    Sma,
+    Ema,
+    Wma,
+    /// Wilder's smoothing (see `smma`).
+    Wilder,
+}
+
+/// Calculate a signal line for a `&[f32]` of indicator values.
+///
+/// ### Definition
+/// Many indicators (MACD, stochastic, TSI, PPO) pair their raw line with a
+/// moving average of that line. Crossings between the two are used as
+/// entry/exit triggers.
+///
+/// ### Alignment
+/// The signal line is shorter than `values` because of the moving average's
+/// warm-up. The first signal value corresponds to:
+/// - `values[period - 1]` for `Smoothing::Sma` and `Smoothing::Wma`
+/// - `values[period]` for `Smoothing::Ema` (the SMA seed is not emitted)
+///
+/// NOTE: Returns an empty `Vec` when there are not enough `values` to
+/// calculate a single signal value rather than panicking, so indicators can
+/// always pair a raw line with its signal line.
+///
+/// # Arguments
+/// * `values` - `&[f32]` containing indicator values for a period of time
+/// * `period` - Number of periods to average
+/// * `method` - Moving average used for the signal line
+///
+/// ### Example
+/// ```
+/// ta::signal_line(&macd, 9, Smoothing::Ema);
+/// ta::signal_line(&stoch, 3, Smoothing::Sma);
+/// ```
+pub fn signal_line(values: &[f32], period: usize, method: Smoothing) -> Vec<f32> {
+    return smooth(values, method, period);
+}
+
+/// Smooth a `&[f32]` of raw indicator values with a moving average.
+///
+/// A generic post-filter so any raw oscillator output can be smoothed
+/// without the indicator itself needing a smoothing parameter.
+///
+/// ### Alignment
+/// The first smoothed value corresponds to:
+/// - `values[period - 1]` for `Smoothing::Sma`, `Smoothing::Wma` and `Smoothing::Wilder`
+/// - `values[period]` for `Smoothing::Ema` (the SMA seed is not emitted)
+///
+/// NOTE: Returns an empty `Vec` when there are not enough `values` to
+/// calculate a single smoothed value.
+///
+/// # Arguments
+/// * `values` - `&[f32]` containing indicator values for a period of time
+/// * `method` - Moving average used to smooth the values
+/// * `period` - Number of periods to average
+///
+/// ### Example
+/// ```
+/// ta::smooth(&stochastic_oscillator::run(prices, 14), Smoothing::Sma, 3);
+/// ```
+pub fn smooth(values: &[f32], method: Smoothing, period: usize) -> Vec<f32> {
+    if period == 0 { return Vec::new(); }
+    match method {
+        Smoothing::Sma => {
+            if values.len() < period { return Vec::new(); }
+            return sma::run(values.to_vec(), period);
+        },
+        Smoothing::Ema => {
+            if values.len() < period+1 { return Vec::new(); }
+            return ema::run(values.to_vec(), period);
+        },
+        Smoothing::Wma => {
+            if values.len() < period { return Vec::new(); }
+            return wma::run(values.to_vec(), period);
+        },
+        Smoothing::Wilder => {
+            if values.len() < period { return Vec::new(); }
+            return smma::run(values.to_vec(), period);
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::ta::{macd, stochastic_oscillator};
+
+    #[test]
+    fn test_signal_line_sma() {
+        let values = vec![10.0, 10.0, 15.0, 20.0, 20.0, 10.0, 10.0, 10.0];
+        assert_eq!(signal_line(&values, 5, Smoothing::Sma), vec![15.0, 15.0, 15.0, 14.0]);
+    }
+
+    #[test]
+    fn test_signal_line_ema_matches_macd() {
+        let prices = vec![
+            10.0, 10.0, 15.0, 20.0, 20.0,
+            12.0, 8.0, 20.0, 42.0, 36.0,
+            11.0, 19.0, 3.0, 4.0, 7.0,
+            10.0, 10.0, 15.0, 20.0, 20.0,
+            12.0, 8.0, 20.0, 42.0, 36.0,
+            11.0, 19.0, 3.0, 4.0, 7.0,
+            10.0, 10.0, 15.0, 20.0, 20.0,
+            12.0, 8.0, 20.0, 42.0, 36.0,
+        ];
+        let (macds, signal) = macd::run(prices);
+        assert_eq!(signal_line(&macds, 9, Smoothing::Ema), signal);
+        assert_eq!(signal_line(&macds, 9, Smoothing::Ema), ema::run(macds.to_vec(), 9));
+        assert_eq!(signal.len(), macds.len() - 9);
+    }
+
+    #[test]
+    fn test_signal_line_not_enough_elements() {
+        assert_eq!(signal_line(&[10.0, 12.0], 5, Smoothing::Sma), Vec::<f32>::new());
+        assert_eq!(signal_line(&[10.0, 12.0, 14.0, 16.0, 18.0], 5, Smoothing::Ema), Vec::<f32>::new());
+    }
+
+    #[test]
+    fn test_smooth_stochastic() {
+        let prices = vec![
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+        ];
+        let k = stochastic_oscillator::run(prices, 14);
+        let mut manual: Vec<f32> = Vec::new();
+        for i in 2..k.len() {
+            manual.push((k[i-2] + k[i-1] + k[i]) / 3.0);
+        }
+        assert_eq!(smooth(&k, Smoothing::Sma, 3), manual);
+    }
+
+    #[test]
+    fn test_smooth_wilder() {
+        let values = vec![10.0, 12.0, 14.0, 16.0, 18.0];
+        assert_eq!(smooth(&values, Smoothing::Wilder, 3), smma::run(values.to_vec(), 3));
+        assert_eq!(smooth(&[10.0, 20.0], Smoothing::Wilder, 3), Vec::<f32>::new());
+    }
+
+    #[test]
+    fn test_smooth_wma() {
+        assert_eq!(smooth(&[10.0, 20.0, 30.0, 30.0, 0.0], Smoothing::Wma, 3), vec![23.333334, 28.333334, 15.0]);
+        assert_eq!(smooth(&[10.0, 20.0], Smoothing::Wma, 3), Vec::<f32>::new());
+    }
+}
@@ -0,0 +1,48 @@
+/// Price of a bar fed into an indicator in place of the raw close.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum PriceSource {
+    /// `c`
+    Close,
+    /// `(h + l + c) / 3`
+    Typical,
+    /// `(h + l) / 2`
+    Median,
+    /// `(h + l + 2c) / 4`
+    WeightedClose,
+}
+
+impl PriceSource {
+    /// Reduce a bar to a single price.
+    ///
+    /// # Arguments
+    /// * `bar` - `(f32, f32, f32)` in the format of `(close, low, high)`
+    ///
+    /// ### Example
+    /// ```
+    /// PriceSource::Typical.price((15.0, 10.0, 20.0));
+    /// ```
+    pub fn price(&self, bar: (f32, f32, f32)) -> f32 {
+        let (close, low, high) = bar;
+        match self {
+            PriceSource::Close => return close,
+            PriceSource::Typical => return (high + low + close) / 3.0,
+            PriceSource::Median => return (high + low) / 2.0,
+            PriceSource::WeightedClose => return (high + low + 2.0 * close) / 4.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_price() {
+        let bar = (18.0, 10.0, 20.0);
+        assert_eq!(PriceSource::Close.price(bar), 18.0);
+        assert_eq!(PriceSource::Typical.price(bar), 16.0);
+        assert_eq!(PriceSource::Median.price(bar), 15.0);
+        assert_eq!(PriceSource::WeightedClose.price(bar), 16.5);
+    }
+}
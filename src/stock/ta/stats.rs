@@ -0,0 +1,733 @@
+use crate::stock::ta::util::{self, InputPolicy, TaError};
+use crate::stock::ta::{alignment, GapPolicy};
+
+/// Method used to calculate the return between two prices.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum ReturnKind {
+    /// `(p / p_prev) - 1`
+    Simple,
+    /// `ln(p / p_prev)`
+    Log,
+}
+
+/// Arithmetic mean of a `&[f32]`.
+///
+/// NOTE: The sum is kept in `f64`, so the mean of a constant series is
+/// exactly its value and the series' deviations, and so its variance, are
+/// exactly `0`. In `f32` a value such as `0.3` repeated leaves a small
+/// residual variance that zero-variance guards would miss.
+pub fn mean(data: &[f32]) -> f32 {
+    let sum: f64 = data.iter().map(|&d| d as f64).sum();
+    return (sum / data.len() as f64) as f32;
+}
+
+/// Quantile `q` (between 0 and 1) of an already sorted `&[f32]` using
+/// linear interpolation between the closest ranks.
+pub fn quantile(sorted: &[f32], q: f32) -> f32 {
+    if sorted.is_empty() { panic!("Not enough entries to calculate a quantile. Received 0, but required 1."); }
+    let rank = q.clamp(0.0, 1.0) * (sorted.len() - 1) as f32;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    return sorted[lower] + ((sorted[upper] - sorted[lower]) * (rank - lower as f32));
+}
+
+/// Population standard deviation of a `&[f32]`.
+pub fn std_dev(data: &[f32]) -> f32 {
+    let m = mean(data);
+    let variance = data.iter().map(|d| {
+        let diff = m - *d;
+        diff * diff
+    }).sum::<f32>() / data.len() as f32;
+    return variance.sqrt();
+}
+
+/// Calculate the rolling Pearson correlation between two `&[f32]` of price data.
+///
+/// ### Definition
+/// Measures how closely two series move together over each window, from
+/// `-1` (perfectly opposite) through `0` (unrelated) to `1` (perfectly
+/// together). Used to find and monitor pairs for pairs trading.
+///
+/// ### Formula
+/// `r = cov(a, b) / (std_dev(a) * std_dev(b))`
+///
+/// NOTE: When either window has zero variance (a flat series) the
+/// correlation is undefined; `0` is emitted instead of `NaN`.
+///
+/// # Arguments
+/// * `a` - `&[f32]` containing prices for a period of time
+/// * `b` - `&[f32]` containing prices for the same period of time
+/// * `period` - Number of periods in each window
+///
+/// ### Example
+/// ```
+/// ta::correlation(&spy, &qqq, 20);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/c/correlationcoefficient.asp
+pub fn correlation(a: &[f32], b: &[f32], period: usize) -> Vec<f32> {
+    if a.len() != b.len() { panic!("Length mismatch. `a` contains {} entries, but `b` contains {}.", a.len(), b.len()); }
+    if period == 0 || a.len() < period { panic!("Not enough entries to calculate the correlation. Received {}, but required {}.", a.len(), period); }
+    let mut correlations: Vec<f32> = Vec::new();
+    for i in period..a.len()+1 {
+        correlations.push(window_correlation(&a[i-period..i], &b[i-period..i]));
+    }
+    return correlations;
+}
+
+/// Calculate the rolling Pearson correlation between two aligned
+/// `&[Option<f32>]` of values whose warm-ups or gaps differ (e.g. padded
+/// with `align`, or from two securities missing different bars).
+///
+/// NOTE: A bar is a gap when either input is `None` or `NaN`. With
+/// `GapPolicy::PropagateNone` the output is aligned with the inputs and is
+/// `None` wherever the window ending at that bar holds a gap (including the
+/// first `period-1` bars). With `GapPolicy::SkipBar` gap bars are left out
+/// and the correlation is calculated over the remaining bars, so the output
+/// only holds one value per full window of them.
+///
+/// # Arguments
+/// * `a` - `&[Option<f32>]` containing values of the first series
+/// * `b` - `&[Option<f32>]` containing values of the second series
+/// * `period` - Number of periods in each window
+/// * `policy` - How bars with a gap in either input are treated
+///
+/// ### Example
+/// ```
+/// ta::correlation_aligned(&spy, &qqq, 20, GapPolicy::SkipBar);
+/// ```
+pub fn correlation_aligned(a: &[Option<f32>], b: &[Option<f32>], period: usize, policy: GapPolicy) -> Vec<Option<f32>> {
+    if a.len() != b.len() { panic!("Length mismatch. `a` contains {} entries, but `b` contains {}.", a.len(), b.len()); }
+    if period == 0 { panic!("Invalid period for correlation. Received 0, but required at least 1."); }
+    let mut correlations: Vec<Option<f32>> = Vec::new();
+    // Bars without a gap, as `(a, b)`
+    let mut wa: Vec<f32> = Vec::new();
+    let mut wb: Vec<f32> = Vec::new();
+    for i in 0..a.len() {
+        match (alignment::present(a[i]), alignment::present(b[i])) {
+            (Some(x), Some(y)) => {
+                wa.push(x);
+                wb.push(y);
+            },
+            _ => {
+                if policy == GapPolicy::PropagateNone {
+                    wa.clear();
+                    wb.clear();
+                    correlations.push(None);
+                }
+                continue;
+            },
+        };
+        if wa.len() > period {
+            wa.remove(0);
+            wb.remove(0);
+        }
+        if wa.len() == period {
+            correlations.push(Some(window_correlation(&wa, &wb)));
+        } else if policy == GapPolicy::PropagateNone {
+            correlations.push(None);
+        }
+    }
+    return correlations;
+}
+
+/// Pearson correlation of two equal length windows, `0` when either has
+/// zero variance.
+fn window_correlation(wa: &[f32], wb: &[f32]) -> f32 {
+    let ma = mean(wa);
+    let mb = mean(wb);
+    let mut cov = 0.0;
+    let mut var_a = 0.0;
+    let mut var_b = 0.0;
+    for j in 0..wa.len() {
+        let da = wa[j] - ma;
+        let db = wb[j] - mb;
+        cov += da * db;
+        var_a += da * da;
+        var_b += db * db;
+    }
+    if var_a == 0.0 || var_b == 0.0 { return 0.0; }
+    return cov / (var_a * var_b).sqrt();
+}
+
+/// Calculate the rolling skewness (third standardized moment) of a `&[f32]`
+/// of data, typically returns.
+///
+/// ### Formula
+/// `skew = mean((x - m)^3) / std_dev^3`
+///
+/// NOTE: The population moments are used. A window with zero variance has
+/// no defined shape; `0` is emitted instead of `NaN`.
+///
+/// ### Usage
+/// A positive skew means a longer right tail: mostly small losses with the
+/// occasional large gain. A negative skew is the opposite, and a warning of
+/// tail risk to the downside.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing data for a period of time (e.g. from `returns`)
+/// * `period` - Number of periods in each window
+///
+/// ### Example
+/// ```
+/// ta::skewness(&ta::returns(&prices, ReturnKind::Log), 60);
+/// ```
+pub fn skewness(prices: &[f32], period: usize) -> Vec<f32> {
+    if period == 0 || prices.len() < period { panic!("Not enough entries to calculate the skewness. Received {}, but required {}.", prices.len(), period); }
+    return prices.windows(period).map(|w| standardized_moment(w, 3).unwrap_or(0.0)).collect();
+}
+
+/// Calculate the rolling excess kurtosis (fourth standardized moment minus
+/// 3) of a `&[f32]` of data, typically returns.
+///
+/// ### Formula
+/// `kurt = (mean((x - m)^4) / std_dev^4) - 3`
+///
+/// NOTE: The population moments are used, and 3 is subtracted so a normal
+/// distribution reads 0. A window with zero variance has no defined shape;
+/// `0` is emitted instead of `NaN`.
+///
+/// ### Usage
+/// A positive excess kurtosis means fatter tails than a normal distribution,
+/// i.e. extreme moves are more likely than the standard deviation suggests.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing data for a period of time (e.g. from `returns`)
+/// * `period` - Number of periods in each window
+///
+/// ### Example
+/// ```
+/// ta::kurtosis(&ta::returns(&prices, ReturnKind::Log), 60);
+/// ```
+pub fn kurtosis(prices: &[f32], period: usize) -> Vec<f32> {
+    if period == 0 || prices.len() < period { panic!("Not enough entries to calculate the kurtosis. Received {}, but required {}.", prices.len(), period); }
+    return prices.windows(period).map(|w| {
+        match standardized_moment(w, 4) {
+            Some(v) => v - 3.0,
+            None => 0.0,
+        }
+    }).collect();
+}
+
+/// `order`-th standardized moment of a window, or `None` with zero variance.
+fn standardized_moment(window: &[f32], order: i32) -> Option<f32> {
+    let m = mean(window);
+    let sd = std_dev(window);
+    if sd == 0.0 { return None; }
+    let moment = window.iter().map(|x| (x - m).powi(order)).sum::<f32>() / window.len() as f32;
+    return Some(moment / sd.powi(order));
+}
+
+/// Calculate the rolling beta of an asset against a benchmark from `&[f32]`
+/// of returns.
+///
+/// ### Definition
+/// Measures the volatility of an asset relative to the market. A beta of
+/// `1` moves with the market, above `1` amplifies the market's moves, and
+/// below `1` dampens them. A negative beta moves against the market.
+///
+/// ### Formula
+/// `beta = cov(asset, market) / var(market)`
+///
+/// NOTE: When the market window has zero variance the beta is undefined;
+/// `0` is emitted instead of `NaN`.
+///
+/// # Arguments
+/// * `asset_returns` - `&[f32]` containing the asset's returns for a period of time
+/// * `market_returns` - `&[f32]` containing the benchmark's returns for the same period of time
+/// * `period` - Number of periods in each window
+///
+/// ### Example
+/// ```
+/// ta::beta(&aapl_returns, &spy_returns, 60);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/b/beta.asp
+pub fn beta(asset_returns: &[f32], market_returns: &[f32], period: usize) -> Vec<f32> {
+    if asset_returns.len() != market_returns.len() { panic!("Length mismatch. `asset_returns` contains {} entries, but `market_returns` contains {}.", asset_returns.len(), market_returns.len()); }
+    if period == 0 || asset_returns.len() < period { panic!("Not enough entries to calculate the beta. Received {}, but required {}.", asset_returns.len(), period); }
+    let mut betas: Vec<f32> = Vec::new();
+    for i in period..asset_returns.len()+1 {
+        let wa = &asset_returns[i-period..i];
+        let wm = &market_returns[i-period..i];
+        let ma = mean(wa);
+        let mm = mean(wm);
+        let mut cov = 0.0;
+        let mut var_m = 0.0;
+        for j in 0..period {
+            let dm = wm[j] - mm;
+            cov += (wa[j] - ma) * dm;
+            var_m += dm * dm;
+        }
+        if var_m == 0.0 {
+            betas.push(0.0);
+        } else {
+            betas.push(cov / var_m);
+        }
+    }
+    return betas;
+}
+
+/// Calculate the period-over-period returns of a `&[f32]` of price data.
+///
+/// ### Formula
+/// - `ReturnKind::Simple`: `r = (p / p_prev) - 1`
+/// - `ReturnKind::Log`: `r = ln(p / p_prev)`
+///
+/// Log returns are additive over time, which makes them the usual input for
+/// volatility calculations. Simple returns compound multiplicatively and are
+/// easier to interpret as a percent change.
+///
+/// NOTE: Returns contain one less entry than `prices`. A previous price of
+/// `0` (or any non-positive price for log returns) has no defined return and
+/// will panic.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `kind` - Method used to calculate each return
+///
+/// ### Example
+/// ```
+/// ta::returns(&prices, ReturnKind::Simple);
+/// ta::returns(&prices, ReturnKind::Log);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/r/rateofreturn.asp
+pub fn returns(prices: &[f32], kind: ReturnKind) -> Vec<f32> {
+    if prices.len() < 2 { panic!("Not enough entries to calculate returns. Received {}, but required 2.", prices.len()); }
+    let mut rets: Vec<f32> = Vec::new();
+    for i in 1..prices.len() {
+        let prev = prices[i-1];
+        let cur = prices[i];
+        match kind {
+            ReturnKind::Simple => {
+                if prev == 0.0 { panic!("Invalid price for simple returns. Received 0 at index {}, but required a non-zero price.", i-1); }
+                rets.push((cur / prev) - 1.0);
+            },
+            ReturnKind::Log => {
+                if prev <= 0.0 || cur <= 0.0 {
+                    let (index, price) = if prev <= 0.0 { (i-1, prev) } else { (i, cur) };
+                    panic!("Invalid price for log returns. Received {} at index {}, but required a positive price.", price, index);
+                }
+                rets.push((cur / prev).ln());
+            },
+        };
+    }
+    return rets;
+}
+
+/// Calculate the returns after applying `policy` to zero or negative
+/// prices, instead of panicking on them like `returns`.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `kind` - Method used to calculate each return
+/// * `policy` - Treatment of zero or negative prices
+///
+/// ### Example
+/// ```
+/// stats::returns_with_policy(&prices, ReturnKind::Log, InputPolicy::Lenient)?;
+/// ```
+pub fn returns_with_policy(prices: &[f32], kind: ReturnKind, policy: InputPolicy) -> Result<Vec<f32>, TaError> {
+    let prices = util::apply_policy(prices, policy)?;
+    util::check_input(&prices, 2)?;
+    return Ok(returns(&prices, kind));
+}
+
+/// Calculate the rolling annualized historical volatility of a `&[f32]` of
+/// price data.
+///
+/// ### Definition
+/// The standard measure of realized volatility: how widely returns have
+/// been dispersed over a recent window, scaled to a yearly figure so
+/// different sampling frequencies can be compared.
+///
+/// ### Formula
+/// `vol = std_dev(ln(p / p_prev)) * sqrt(n)`
+///
+/// - `std_dev`: population standard deviation of the log returns in the window
+/// - `n`: number of periods per year (e.g. 252 for daily bars)
+///
+/// NOTE: Each window holds `period` returns, which requires `period + 1`
+/// prices, so the first value corresponds to `prices[period]`.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `period` - Number of returns in each window
+/// * `periods_per_year` - Number of periods in a year used to annualize
+///
+/// ### Example
+/// ```
+/// ta::volatility(&prices, 20, 252.0);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/h/historicalvolatility.asp
+pub fn volatility(prices: &[f32], period: usize, periods_per_year: f32) -> Vec<f32> {
+    if period == 0 || prices.len() < period+1 { panic!("Not enough entries to calculate the volatility. Received {}, but required {} (period+1).", prices.len(), period+1); }
+    let rets = returns(prices, ReturnKind::Log);
+    let annualization = periods_per_year.sqrt();
+    let mut vols: Vec<f32> = Vec::new();
+    for i in period..rets.len()+1 {
+        vols.push(std_dev(&rets[i-period..i]) * annualization);
+    }
+    return vols;
+}
+
+/// Calculate the maximum drawdown of a `&[f32]` of price data.
+///
+/// ### Definition
+/// The largest peak-to-trough decline over the series, before a new peak is
+/// reached. A key risk metric for how much an investor could have lost by
+/// buying at the worst possible time.
+///
+/// ### Formula
+/// `mdd = max((peak - p) / peak)`
+///
+/// - `peak`: highest price seen up to and including `p`
+///
+/// # Returns
+/// `(f32, usize, usize)` in the form of `(drawdown, peak_index, trough_index)`
+/// where the drawdown is a positive fraction (e.g. `0.25` for a 25% decline).
+/// A series that never declines (or is empty) returns `(0.0, 0, 0)`.
+///
+/// NOTE: A decline from a peak of 0 (e.g. a series starting at 0 or an
+/// equity curve that has hit 0) has no defined size and is skipped.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+///
+/// ### Example
+/// ```
+/// let (drawdown, peak, trough) = ta::max_drawdown(&prices);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/m/maximum-drawdown-mdd.asp
+pub fn max_drawdown(prices: &[f32]) -> (f32, usize, usize) {
+    let mut max_dd: (f32, usize, usize) = (0.0, 0, 0);
+    let mut peak_index = 0;
+    for i in 0..prices.len() {
+        if prices[i] > prices[peak_index] {
+            peak_index = i;
+            continue;
+        }
+        if prices[peak_index] == 0.0 { continue; }
+        let dd = (prices[peak_index] - prices[i]) / prices[peak_index];
+        if dd > max_dd.0 {
+            max_dd = (dd, peak_index, i);
+        }
+    }
+    return max_dd;
+}
+
+/// Calculate the annualized Sharpe ratio of a `&[f32]` of returns.
+///
+/// ### Definition
+/// Measures the excess return earned per unit of risk (volatility). Higher
+/// is better; it is commonly used to score and compare backtests.
+///
+/// ### Formula
+/// `sharpe = (mean(r - rf) / std_dev(r)) * sqrt(n)`
+///
+/// - `r`: returns for each period (see `returns`)
+/// - `rf`: risk-free return for a single period (e.g. the annual rate / 252 for daily returns)
+/// - `n`: number of periods per year
+///
+/// NOTE: A series with zero variance, e.g. a constant return, has an
+/// undefined Sharpe ratio; `0` is returned instead of `NaN`/infinity.
+///
+/// # Arguments
+/// * `returns` - `&[f32]` containing returns for a period of time
+/// * `risk_free` - Risk-free return per period
+/// * `periods_per_year` - Number of periods in a year used to annualize
+///
+/// ### Example
+/// ```
+/// ta::sharpe(&ta::returns(&prices, ReturnKind::Simple), 0.04 / 252.0, 252.0);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/s/sharperatio.asp
+pub fn sharpe(returns: &[f32], risk_free: f32, periods_per_year: f32) -> f32 {
+    if returns.is_empty() { panic!("Not enough entries to calculate the Sharpe ratio. Received {}, but required 1.", returns.len()); }
+    let excess: Vec<f32> = returns.iter().map(|r| r - risk_free).collect();
+    let std = std_dev(returns);
+    if std == 0.0 { return 0.0; }
+    return (mean(&excess) / std) * periods_per_year.sqrt();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_correlation_positive() {
+        let a = vec![10.0, 12.0, 11.0, 15.0, 14.0];
+        let b = vec![20.0, 24.0, 22.0, 30.0, 28.0];
+        assert_eq!(correlation(&a, &b, 3), vec![1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_correlation_negative() {
+        let a = vec![10.0, 12.0, 11.0, 15.0, 14.0];
+        let b = vec![-10.0, -12.0, -11.0, -15.0, -14.0];
+        assert_eq!(correlation(&a, &b, 3), vec![-1.0, -1.0, -1.0]);
+    }
+
+    #[test]
+    fn test_skewness_symmetric() {
+        let data = vec![1.0, 2.0, 3.0, 4.0, 5.0, 4.0, 3.0, 2.0, 1.0];
+        let skews = skewness(&data, 5);
+        assert_eq!(skews.len(), 5);
+        assert_eq!(skews[0].abs() < 1e-5, true);
+        assert_eq!(skews[4].abs() < 1e-5, true);
+    }
+
+    #[test]
+    fn test_skewness_right_skewed() {
+        // Mostly small values with one large outlier
+        let data = vec![1.0, 1.0, 2.0, 1.0, 2.0, 1.0, 10.0];
+        let skews = skewness(&data, 7);
+        assert_eq!(skews[0] > 1.0, true);
+        let flipped: Vec<f32> = data.iter().map(|d| -d).collect();
+        assert_eq!((skewness(&flipped, 7)[0] + skews[0]).abs() < 1e-5, true);
+    }
+
+    #[test]
+    fn test_kurtosis() {
+        // Two-point distribution: the lowest possible kurtosis
+        let flat = vec![-1.0, 1.0, -1.0, 1.0];
+        assert_eq!((kurtosis(&flat, 4)[0] + 2.0).abs() < 1e-5, true);
+        // A single outlier makes the tails fat
+        let fat = vec![0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 10.0];
+        assert_eq!(kurtosis(&fat, 10)[0] > 3.0, true);
+    }
+
+    #[test]
+    fn test_moments_zero_variance() {
+        let data = vec![5.0, 5.0, 5.0, 5.0];
+        assert_eq!(skewness(&data, 3), vec![0.0, 0.0]);
+        assert_eq!(kurtosis(&data, 3), vec![0.0, 0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the skewness. Received 2, but required 3.")]
+    fn test_skewness_not_enough_elements() {
+        skewness(&[1.0, 2.0], 3);
+    }
+
+    #[test]
+    fn test_correlation_zero_variance() {
+        let a = vec![10.0, 12.0, 11.0, 15.0];
+        let b = vec![5.0, 5.0, 5.0, 6.0];
+        assert_eq!(correlation(&a, &b, 3)[0], 0.0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `a` contains 2 entries, but `b` contains 1.")]
+    fn test_correlation_length_mismatch() {
+        correlation(&[10.0, 10.0], &[10.0], 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the correlation. Received 1, but required 3.")]
+    fn test_correlation_not_enough_elements() {
+        correlation(&[10.0], &[10.0], 3);
+    }
+
+    #[test]
+    fn test_beta_double_market() {
+        let market = vec![0.01, -0.02, 0.015, 0.005, -0.01, 0.02];
+        let asset: Vec<f32> = market.iter().map(|r| r * 2.0).collect();
+        let betas = beta(&asset, &market, 4);
+        assert_eq!(betas.len(), 3);
+        for b in betas {
+            assert_eq!((b - 2.0).abs() < 0.0001, true);
+        }
+    }
+
+    #[test]
+    fn test_beta_zero_market_variance() {
+        assert_eq!(beta(&[0.01, 0.02, 0.03], &[0.01, 0.01, 0.01], 3), vec![0.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `asset_returns` contains 2 entries, but `market_returns` contains 1.")]
+    fn test_beta_length_mismatch() {
+        beta(&[0.01, 0.02], &[0.01], 1);
+    }
+
+    #[test]
+    fn test_returns_simple() {
+        assert_eq!(returns(&[100.0, 110.0, 99.0], ReturnKind::Simple), vec![0.100000024, -0.100000024]);
+    }
+
+    #[test]
+    fn test_returns_log() {
+        let rets = returns(&[100.0, 110.0, 99.0], ReturnKind::Log);
+        assert_eq!(rets, vec![0.095310204, -0.105360545]);
+        // Log returns are smaller than simple returns on gains, larger in magnitude on losses
+        let simple = returns(&[100.0, 110.0, 99.0], ReturnKind::Simple);
+        assert_eq!(rets[0] < simple[0], true);
+        assert_eq!(rets[1] < simple[1], true);
+        // and are additive
+        let total = returns(&[100.0, 99.0], ReturnKind::Log)[0];
+        assert_eq!((rets[0] + rets[1] - total).abs() < 0.000001, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid price for log returns. Received -5 at index 2, but required a positive price.")]
+    fn test_returns_log_negative_price() {
+        returns(&[100.0, 110.0, -5.0], ReturnKind::Log);
+    }
+
+    #[test]
+    fn test_returns_with_policy_negative_price() {
+        let prices = vec![100.0, 110.0, -5.0];
+        assert_eq!(returns_with_policy(&prices, ReturnKind::Log, InputPolicy::Strict), Err(TaError::NonPositive { index: 2, value: -5.0 }));
+        let rets = returns_with_policy(&prices, ReturnKind::Log, InputPolicy::Lenient).unwrap();
+        assert_eq!(rets, returns(&[100.0, 110.0, util::MIN_PRICE], ReturnKind::Log));
+        assert_eq!(rets.iter().all(|r| r.is_finite()), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid price for simple returns. Received 0 at index 0, but required a non-zero price.")]
+    fn test_returns_simple_zero_price() {
+        returns(&[0.0, 110.0], ReturnKind::Simple);
+    }
+
+    #[test]
+    fn test_std_dev() {
+        assert_eq!(std_dev(&[2.0, 4.0, 4.0, 4.0, 5.0, 5.0, 7.0, 9.0]), 2.0);
+    }
+
+    #[test]
+    fn test_volatility_annualization() {
+        // Log returns alternate between +0.01 and -0.01, so their standard deviation is 0.01
+        let mut prices = vec![100.0];
+        for i in 0..10 {
+            let r: f32 = if i % 2 == 0 { 0.01 } else { -0.01 };
+            let last = prices[prices.len()-1];
+            prices.push(last * r.exp());
+        }
+        let daily = volatility(&prices, 4, 1.0);
+        let annual = volatility(&prices, 4, 252.0);
+        assert_eq!(daily.len(), 7);
+        for i in 0..daily.len() {
+            assert_eq!((daily[i] - 0.01).abs() < 0.0001, true);
+            assert_eq!((annual[i] - (0.01 * 252.0_f32.sqrt())).abs() < 0.001, true);
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the volatility. Received 3, but required 4 (period+1).")]
+    fn test_volatility_not_enough_elements() {
+        volatility(&[10.0, 11.0, 12.0], 3, 252.0);
+    }
+
+    #[test]
+    fn test_max_drawdown() {
+        // 10% drawdown from index 1 to 2, then a 40% drawdown from index 4 to 6
+        let prices = vec![90.0, 100.0, 90.0, 110.0, 150.0, 120.0, 90.0, 130.0];
+        assert_eq!(max_drawdown(&prices), (0.4, 4, 6));
+    }
+
+    #[test]
+    fn test_max_drawdown_monotonic() {
+        assert_eq!(max_drawdown(&[10.0, 11.0, 12.0, 13.0]), (0.0, 0, 0));
+        assert_eq!(max_drawdown(&[]), (0.0, 0, 0));
+    }
+
+    #[test]
+    fn test_max_drawdown_zero_peak() {
+        assert_eq!(max_drawdown(&[0.0, 0.0, -1.0]), (0.0, 0, 0));
+        // An equity curve wiped out, then recovering to a new peak
+        assert_eq!(max_drawdown(&[100.0, 0.0, 0.0, 50.0, 40.0]), (1.0, 0, 1));
+    }
+
+    #[test]
+    fn test_sharpe() {
+        // mean = 0.0125, std_dev = 0.0147902
+        let rets = vec![0.01, 0.02, -0.01, 0.03];
+        assert_eq!((sharpe(&rets, 0.0, 1.0) - 0.845154).abs() < 0.0001, true);
+        assert_eq!((sharpe(&rets, 0.0, 252.0) - 13.416408).abs() < 0.001, true);
+        assert_eq!(sharpe(&rets, 0.005, 1.0) < sharpe(&rets, 0.0, 1.0), true);
+    }
+
+    #[test]
+    fn test_sharpe_constant_returns() {
+        assert_eq!(sharpe(&[0.01, 0.01, 0.01, 0.01], 0.0, 252.0), 0.0);
+        assert_eq!(sharpe(&[0.3; 7], 0.0, 252.0), 0.0);
+    }
+
+    #[test]
+    fn test_sharpe_small_returns() {
+        // std_dev is below f32::EPSILON but the returns still vary
+        let rets = vec![1e-8, 2e-8, -1e-8, 3e-8];
+        assert_eq!((sharpe(&rets, 0.0, 1.0) - 0.845154).abs() < 0.001, true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the Sharpe ratio. Received 0, but required 1.")]
+    fn test_sharpe_not_enough_elements() {
+        sharpe(&[], 0.0, 252.0);
+    }
+
+    #[test]
+    fn test_quantile() {
+        let sorted = vec![1.0, 2.0, 3.0, 4.0, 5.0];
+        assert_eq!(quantile(&sorted, 0.0), 1.0);
+        assert_eq!(quantile(&sorted, 0.5), 3.0);
+        assert_eq!(quantile(&sorted, 0.875), 4.5);
+        assert_eq!(quantile(&sorted, 1.0), 5.0);
+    }
+
+    #[test]
+    fn test_beta_flat_market() {
+        // 0.3 is not representable, so an f32 mean would leave a residual variance
+        let asset = vec![0.01, -0.02, 0.015, 0.005, -0.01, 0.02, 0.0];
+        assert_eq!(beta(&asset, &[0.3; 7], 7), vec![0.0]);
+    }
+
+    #[test]
+    fn test_skewness_kurtosis_flat_window() {
+        assert_eq!(skewness(&[0.3; 7], 7), vec![0.0]);
+        assert_eq!(kurtosis(&[0.3; 7], 7), vec![0.0]);
+    }
+
+    #[test]
+    fn test_correlation_flat_window() {
+        let a = vec![10.0, 12.0, 14.0, 11.0, 13.0, 10.0, 12.0];
+        assert_eq!(correlation(&a, &[0.3; 7], 7), vec![0.0]);
+        assert_eq!(correlation(&[0.3; 7], &a, 7), vec![0.0]);
+    }
+
+    #[test]
+    fn test_correlation_aligned_mismatched_timestamps() {
+        // `a` is missing bar 2 and `b` has no quote yet on bar 0
+        let a = vec![Some(10.0), Some(11.0), None, Some(12.0), Some(14.0), Some(13.0), Some(15.0)];
+        let b = vec![None, Some(20.0), Some(21.0), Some(23.0), Some(22.0), Some(25.0), Some(26.0)];
+        let r = correlation(&[12.0, 14.0, 13.0, 15.0], &[23.0, 22.0, 25.0, 26.0], 3);
+        assert_eq!(
+            correlation_aligned(&a, &b, 3, GapPolicy::PropagateNone),
+            vec![None, None, None, None, None, Some(r[0]), Some(r[1])]
+        );
+        let r = correlation(&[11.0, 12.0, 14.0, 13.0, 15.0], &[20.0, 23.0, 22.0, 25.0, 26.0], 3);
+        assert_eq!(correlation_aligned(&a, &b, 3, GapPolicy::SkipBar), r.into_iter().map(Some).collect::<Vec<Option<f32>>>());
+    }
+
+    #[test]
+    fn test_correlation_aligned_matches_correlation() {
+        let a = vec![10.0, 12.0, 14.0, 11.0, 13.0, 10.0, 12.0];
+        let b = vec![20.0, 25.0, 27.0, 21.0, 22.0, 24.0, 20.0];
+        let a_aligned: Vec<Option<f32>> = a.iter().map(|&v| Some(v)).collect();
+        let b_aligned: Vec<Option<f32>> = b.iter().map(|&v| Some(v)).collect();
+        let expected: Vec<Option<f32>> = correlation(&a, &b, 4).into_iter().map(Some).collect();
+        assert_eq!(correlation_aligned(&a_aligned, &b_aligned, 4, GapPolicy::SkipBar), expected);
+    }
+}
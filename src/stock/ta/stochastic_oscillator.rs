@@ -1,3 +1,7 @@
+use crate::stock::historical::Candle;
+use crate::stock::ta::{extract, Indicator, PriceSource, Series};
+use crate::stock::ta::util::{self, RollingMax, RollingMin, TaError};
+
 /// Calculate the stochasitc oscillator based on a `Vec<(f32, f32, f32)>` of
 /// price data.
 /// 
@@ -11,8 +15,11 @@
 /// 
 /// - `%K`: current value of the stochastic indicator
 /// - `c`: current price
-/// - `h14`: highest price during last 14 trading sessions
-/// - `l14`: lowest price during last 14 trading sessions
+/// - `h14`: highest price during last 14 (`period`) trading sessions
+/// - `l14`: lowest price during last 14 (`period`) trading sessions
+/// 
+/// NOTE: With a `period` of 1 the window only contains the current bar, so
+/// the oscillator degenerates to `((c - l) / (h - l)) * 100` of that bar.
 /// 
 /// ### Usage
 /// Typically, when the stochastic oscillator is greater than 80, the security
@@ -28,47 +35,239 @@
 /// 
 /// # Arguments
 /// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
-///              in the format of `Vec<(close, low, high)>`
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
 /// 
 /// ### Example
 /// ```
-/// stochastic_oscillator::run(prices);
-/// stochastic_oscillator::run(prices);
+/// stochastic_oscillator::run(prices, 14);
+/// stochastic_oscillator::run(prices, 5);
 /// ```
 /// 
 /// #### Resources
 /// - https://www.investopedia.com/terms/s/stochasticoscillator.asp
-pub fn run(prices: Vec<(f32, f32, f32)>) -> Vec<f32> {
-    const PERIOD: usize = 14;
-    if prices.len() < PERIOD { panic!("Not enough entries to calculate stochastic oscillator. Received {}, but required {}.", prices.len(), PERIOD); }
-    let mut oscs: Vec<f32> = Vec::new();
+pub fn run(prices: Vec<(f32, f32, f32)>, period: usize) -> Vec<f32> {
+    return run_with_source(prices, period, PriceSource::Close);
+}
 
-    for i in PERIOD-1..prices.len() {
-        let cur = match prices.get(i) {
-            Some(&v) => v,
-            None => panic!("Could not get entry in `prices`."),
+/// Calculate the stochastic oscillator using `source` in place of the close
+/// price. The high/low window is unchanged.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
+/// * `source` - Price of the current bar compared against the window
+///
+/// ### Example
+/// ```
+/// stochastic_oscillator::run_with_source(prices, 14, PriceSource::Typical);
+/// ```
+pub fn run_with_source(prices: Vec<(f32, f32, f32)>, period: usize, source: PriceSource) -> Vec<f32> {
+    // Index of the first full window; a zero period has no valid window
+    let first = match period.checked_sub(1) {
+        Some(v) => v,
+        None => panic!("Invalid period for stochastic oscillator. Received 0, but required at least 1."),
+    };
+    match util::check_bars(&prices, period) {
+        Ok(_) => {},
+        Err(TaError::NotEnoughEntries { received, required }) => panic!("Not enough entries to calculate stochastic oscillator. Received {}, but required {}.", received, required),
+        Err(error) => panic!("{}", error),
+    };
+    let mut oscs: Vec<f32> = Vec::new();
+    // Highest high/lowest low of the last `period` bars in amortized O(1)
+    let mut highs = RollingMax::new(period);
+    let mut lows = RollingMin::new(period);
+    for (i, &cur) in prices.iter().enumerate() {
+        highs.push(cur.2);
+        lows.push(cur.1);
+        if i < first { continue; }
+        let (low14, high14) = match (lows.value(), highs.value()) {
+            (Some(l), Some(h)) => (l, h),
+            _ => panic!("Could not get entry in `prices`."),
         };
-        let p = cur.0;
-        let mut low14 = cur.1;
-        let mut high14 = cur.2;
-        for j in i+1-PERIOD..i {
-            let prev = match prices.get(j) {
-                Some(&v) => v,
-                None => panic!("Could not get entry in `prices`."),
-            };
-            if low14 > prev.1 { low14 = prev.1; }
-            if high14 < prev.2 { high14 = prev.2; }
-        }
+        let p = source.price(cur);
         let osc = ((p - low14) / (high14 - low14)) * 100.0;
         oscs.push(osc);
     }
     return oscs;
 }
 
+/// Calculate the stochastic oscillator as a `Series`, whose `start_index` is
+/// `period - 1` (the first full high/low window).
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
+///
+/// ### Example
+/// ```
+/// stochastic_oscillator::run_series(prices, 14);
+/// ```
+pub fn run_series(prices: Vec<(f32, f32, f32)>, period: usize) -> Series {
+    let values = run(prices, period);
+    return Series { values: values, start_index: period - 1 };
+}
+
+/// Calculate the stochastic oscillator on a `Vec<(f32, f32, f32)>` of price
+/// data in the `(high, low, close)` layout used by many data sources.
+///
+/// NOTE: `run` expects `(close, low, high)`. Passing one layout to the
+/// other's function does not fail, it just silently produces wrong values.
+/// Where possible use `run_candles` instead, which has no tuple layout.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(high, low, close)>`
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
+///
+/// ### Example
+/// ```
+/// stochastic_oscillator::run_hlc(prices, 14);
+/// ```
+pub fn run_hlc(prices: Vec<(f32, f32, f32)>, period: usize) -> Vec<f32> {
+    return run(prices.iter().map(|&(high, low, close)| (close, low, high)).collect(), period);
+}
+
+/// Calculate the stochastic oscillator on a `&[Candle]` of price data.
+///
+/// # Arguments
+/// * `candles` - `&[Candle]` containing prices for a period of time
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
+///
+/// ### Example
+/// ```
+/// stochastic_oscillator::run_candles(stock.get_historical_data(), 14);
+/// ```
+pub fn run_candles(candles: &[Candle], period: usize) -> Vec<f32> {
+    return run(extract::close_low_highs(candles), period);
+}
+
+/// Calculate the stochastic oscillator using the weighted close
+/// `(h + l + 2c) / 4` in place of the close price, which dampens the noise
+/// of closes at the extremes of a bar. The high/low window is unchanged.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
+///
+/// ### Example
+/// ```
+/// stochastic_oscillator::run_weighted(prices, 14);
+/// ```
+pub fn run_weighted(prices: Vec<(f32, f32, f32)>, period: usize) -> Vec<f32> {
+    return run_with_source(prices, period, PriceSource::WeightedClose);
+}
+
+/// Calculate the stochastic oscillator, optionally clamping every value to
+/// [0-100].
+///
+/// On thin instruments bad ticks can leave a close outside the bar's own
+/// high/low, briefly pushing the oscillator above 100 or below 0. Clamping
+/// keeps such dirty data from triggering overbought/oversold rules or
+/// skewing a chart's scale. With `clamp` off this is the same as `run`.
+///
+/// NOTE: `NaN` values (a window without range) are left untouched.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
+/// * `clamp` - Whether to clamp each value to [0-100]
+///
+/// ### Example
+/// ```
+/// stochastic_oscillator::run_with_clamp(prices, 14, true);
+/// ```
+pub fn run_with_clamp(prices: Vec<(f32, f32, f32)>, period: usize, clamp: bool) -> Vec<f32> {
+    let mut oscs = run(prices, period);
+    if clamp {
+        for osc in oscs.iter_mut() {
+            *osc = osc.clamp(0.0, 100.0);
+        }
+    }
+    return oscs;
+}
+
+/// Calculate the stochastic oscillator on a `Vec<(i64, i64, i64)>` of
+/// prices stored as integer cents.
+///
+/// The window's highest high and lowest low and both differences are exact
+/// integers; only the final ratio is floating point.
+///
+/// # Arguments
+/// * `prices` - `Vec<(i64, i64, i64)>` containing prices in cents for a period
+///   of time in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
+///
+/// ### Example
+/// ```
+/// stochastic_oscillator::run_cents(vec![(10137, 10120, 10150), ...], 14);
+/// ```
+pub fn run_cents(prices: Vec<(i64, i64, i64)>, period: usize) -> Vec<f32> {
+    if period == 0 { panic!("Invalid period for stochastic oscillator. Received 0, but required at least 1."); }
+    if prices.len() < period { panic!("Not enough entries to calculate stochastic oscillator. Received {}, but required {}.", prices.len(), period); }
+    let mut oscs: Vec<f32> = Vec::new();
+    let mut highs: RollingMax<i64> = RollingMax::new(period);
+    let mut lows: RollingMin<i64> = RollingMin::new(period);
+    for (i, &(close, low, high)) in prices.iter().enumerate() {
+        highs.push(high);
+        lows.push(low);
+        if i < period-1 { continue; }
+        let (low14, high14) = match (lows.value(), highs.value()) {
+            (Some(l), Some(h)) => (l, h),
+            _ => panic!("Could not get entry in `prices`."),
+        };
+        let osc = ((close - low14) as f64 / (high14 - low14) as f64) * 100.0;
+        oscs.push(osc as f32);
+    }
+    return oscs;
+}
+
+/// `Indicator` implementation of the stochastic oscillator's `run`.
+pub struct StochasticOscillator {
+    pub period: usize,
+}
+
+impl Indicator for StochasticOscillator {
+    type Input = Vec<(f32, f32, f32)>;
+    type Output = Vec<f32>;
+
+    fn compute(&self, data: &Self::Input) -> Self::Output { return run(data.to_vec(), self.period); }
+    fn warmup(&self) -> usize { return self.period.saturating_sub(1); }
+}
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stock::testutil::{assert_close, geometric_brownian};
+
+    const EPSILON: f32 = 1e-4;
+
+    /// Previous O(n * period) implementation scanning the whole window each bar.
+    fn naive(prices: &[(f32, f32, f32)], period: usize) -> Vec<f32> {
+        let mut oscs: Vec<f32> = Vec::new();
+        for i in period-1..prices.len() {
+            let mut low = prices[i].1;
+            let mut high = prices[i].2;
+            for j in (i+1).saturating_sub(period)..i {
+                if low > prices[j].1 { low = prices[j].1; }
+                if high < prices[j].2 { high = prices[j].2; }
+            }
+            oscs.push(((prices[i].0 - low) / (high - low)) * 100.0);
+        }
+        return oscs;
+    }
+
+    fn synthetic(n: usize) -> Vec<(f32, f32, f32)> {
+        let closes = geometric_brownian(42, n, 0.0002, 0.01, 100.0);
+        return closes.iter().enumerate().map(|(i, &c)| {
+            let spread = c * 0.005 * (1.0 + (i % 7) as f32);
+            (c, c - spread, c + spread * 0.8)
+        }).collect();
+    }
 
     #[test]
     fn test_run_simple() {
@@ -81,7 +280,7 @@ mod tests {
             (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
             (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
         ];
-        assert_eq!(run(prices), vec![66.66667]);
+        assert_close(&run(prices, 14), &[66.66667], EPSILON);
     }
 
     #[test]
@@ -97,12 +296,153 @@ mod tests {
             (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
             (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
         ];
-        assert_eq!(run(prices), vec![36.363636, 36.363636, 50.0, 9.090909, 18.181818]);
+        assert_close(&run(prices, 14), &[36.363636, 36.363636, 50.0, 9.090909, 18.181818], EPSILON);
+    }
+
+    #[test]
+    fn test_run_series() {
+        let prices = synthetic(30);
+        let series = run_series(prices.to_vec(), 14);
+        assert_eq!(series.start_index, 13);
+        assert_eq!(series.values, run(prices.to_vec(), 14));
+        assert_eq!(series.start_index + series.values.len(), prices.len());
+    }
+
+    #[test]
+    fn test_run_matches_naive() {
+        let prices = synthetic(2000);
+        for &period in [1, 5, 14, 100].iter() {
+            assert_eq!(run(prices.to_vec(), period), naive(&prices, period));
+        }
+    }
+
+    #[test]
+    fn test_run_large_input() {
+        // O(n) regardless of the period; the naive scan would take 500M steps
+        let prices = synthetic(500_000);
+        let oscs = run(prices, 1000);
+        assert_eq!(oscs.len(), 500_000 - 999);
+        assert_eq!(oscs.iter().all(|&o| (0.0..=100.0).contains(&o)), true);
+    }
+
+    #[test]
+    fn test_run_with_source() {
+        let prices = [
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+        ];
+        assert_eq!(run_with_source(prices.to_vec(), 3, PriceSource::Close), run(prices.to_vec(), 3));
+        // (14 + 13 + 27) / 3 = 18 within a [10, 32] window
+        assert_close(&run_with_source(prices.to_vec(), 3, PriceSource::Typical)[3..4], &[36.363636], EPSILON);
+        assert_ne!(run_with_source(prices.to_vec(), 3, PriceSource::Typical), run(prices.to_vec(), 3));
+    }
+
+    #[test]
+    fn test_run_weighted() {
+        let prices = vec![
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+        ];
+        let weighted = run_weighted(prices.to_vec(), 14);
+        let standard = run(prices.to_vec(), 14);
+        assert_eq!(weighted.len(), standard.len());
+        // (22 + 13 + 2 * 18) / 4 = 17.75 against the close of 18 in a [10, 32] window
+        assert_close(&weighted[0..1], &[35.227272], EPSILON);
+        assert_close(&standard[0..1], &[36.363636], EPSILON);
+        assert_close(&weighted, &[35.227272, 28.40909, 42.045452, 29.545454, 31.818182], EPSILON);
     }
 
     #[test]
     #[should_panic(expected = "Not enough entries to calculate stochastic oscillator. Received 1, but required 14.")]
     fn test_run_not_enough_elements() {
-        run(vec![(10.0, 10.0, 10.0)]);
+        run(vec![(10.0, 10.0, 10.0)], 14);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid entry at index 1. Received inf, but required a finite value.")]
+    fn test_run_infinite() {
+        run(vec![(15.0, 10.0, 20.0), (18.0, 13.0, f32::INFINITY)], 1);
+    }
+
+    #[test]
+    fn test_run_with_clamp() {
+        // Bad ticks leave a close above its high, then one below its low
+        let prices = vec![(10.0, 9.0, 11.0), (12.0, 10.0, 11.0), (8.0, 8.5, 11.0)];
+        assert_eq!(run(prices.to_vec(), 2), vec![150.0, -20.0]);
+        assert_eq!(run_with_clamp(prices.to_vec(), 2, true), vec![100.0, 0.0]);
+        assert_eq!(run_with_clamp(prices.to_vec(), 2, false), run(prices, 2));
+    }
+
+    #[test]
+    fn test_run_cents_matches_float() {
+        let prices = synthetic(200);
+        let cents: Vec<(i64, i64, i64)> = prices.iter().map(|p| {
+            ((p.0 * 100.0).round() as i64, (p.1 * 100.0).round() as i64, (p.2 * 100.0).round() as i64)
+        }).collect();
+        let rounded: Vec<(f32, f32, f32)> = cents.iter().map(|c| (c.0 as f32 / 100.0, c.1 as f32 / 100.0, c.2 as f32 / 100.0)).collect();
+        assert_close(&run_cents(cents, 14), &run(rounded, 14), 1e-2);
+    }
+
+    #[test]
+    fn test_run_cents_simple() {
+        let prices = vec![(1500, 1000, 2000), (1800, 1300, 2200)];
+        assert_eq!(run_cents(prices, 2), vec![66.666664]);
+    }
+
+    #[test]
+    fn test_run_hlc_matches_run() {
+        let prices = vec![
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+        ];
+        let hlc: Vec<(f32, f32, f32)> = prices.iter().map(|&(c, l, h)| (h, l, c)).collect();
+        assert_eq!(run_hlc(hlc.to_vec(), 5), run(prices.to_vec(), 5));
+        // Feeding the `(high, low, close)` layout to `run` is silently wrong
+        assert_ne!(run(hlc, 5), run(prices, 5));
+    }
+
+    #[test]
+    fn test_run_candles() {
+        let candles = vec![
+            Candle::new("4/22/2021 16:00:00", 10.0, 20.0, 10.0, 15.0, 100),
+            Candle::new("4/23/2021 16:00:00", 15.0, 22.0, 13.0, 18.0, 100),
+        ];
+        assert_eq!(run_candles(&candles, 2), run(vec![(15.0, 10.0, 20.0), (18.0, 13.0, 22.0)], 2));
+    }
+
+    #[test]
+    fn test_run_single_period() {
+        // Degenerates to the position of the close within each bar's own range
+        assert_close(&run(vec![(15.0, 10.0, 20.0), (21.0, 13.0, 22.0)], 1), &[50.0, 88.88889], EPSILON);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid period for stochastic oscillator. Received 0, but required at least 1.")]
+    fn test_run_zero_period() {
+        run(vec![(10.0, 10.0, 10.0)], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid period for stochastic oscillator. Received 0, but required at least 1.")]
+    fn test_run_zero_period_empty_prices() {
+        run(vec![], 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid period for stochastic oscillator. Received 0, but required at least 1.")]
+    fn test_indicator_zero_period() {
+        let s = StochasticOscillator { period: 0 };
+        assert_eq!(s.warmup(), 0);
+        s.compute(&vec![(10.0, 10.0, 10.0)]);
     }
 }
\ No newline at end of file
@@ -0,0 +1,57 @@
+/// Count, for every bar, how many consecutive bars a `&[f32]` of indicator
+/// values has been beyond `level`.
+///
+/// With `above` the count is of consecutive values greater than `level`
+/// (e.g. an RSI above 70), otherwise of values less than `level`. A value
+/// on the other side of (or equal to) `level` resets the count to 0.
+///
+/// ### Usage
+/// An oscillator staying overbought for a long time is not a sell signal
+/// but a sign of a strong trend. Exits are often only considered once the
+/// duration drops back to 0.
+///
+/// # Arguments
+/// * `values` - `&[f32]` containing indicator values for a period of time
+/// * `level` - Threshold to compare against (e.g. 70 or 30 for the RSI)
+/// * `above` - Whether to count bars above (`true`) or below (`false`) `level`
+///
+/// # Returns
+/// `Vec<usize>` with one count per entry of `values`.
+///
+/// ### Example
+/// ```
+/// ta::threshold_duration(&rsi::run(prices, 14), 70.0, true);
+/// ```
+pub fn threshold_duration(values: &[f32], level: f32, above: bool) -> Vec<usize> {
+    let mut durations: Vec<usize> = Vec::new();
+    let mut count = 0;
+    for &v in values.iter() {
+        let beyond = if above { v > level } else { v < level };
+        count = if beyond { count + 1 } else { 0 };
+        durations.push(count);
+    }
+    return durations;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::stock::ta::rsi;
+
+    #[test]
+    fn test_threshold_duration_rsi_overbought() {
+        let mut prices: Vec<f32> = vec![10.0, 11.0, 10.0, 11.0, 10.0, 11.0];
+        prices.extend(vec![12.0, 13.0, 14.0, 15.0, 16.0, 15.5, 10.0]);
+        let rsis = rsi::run(prices, 5);
+        let durations = threshold_duration(&rsis, 70.0, true);
+        assert_eq!(rsis.len(), 8);
+        // Overbought from the third rally bar, through the shallow pullback
+        assert_eq!(durations, vec![0, 0, 1, 2, 3, 4, 5, 0]);
+    }
+
+    #[test]
+    fn test_threshold_duration_below() {
+        let values = vec![35.0, 25.0, 20.0, 30.0, 28.0];
+        assert_eq!(threshold_duration(&values, 30.0, false), vec![0, 1, 2, 0, 1]);
+    }
+}
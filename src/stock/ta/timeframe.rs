@@ -0,0 +1,53 @@
+/// Project a higher-timeframe series (e.g. a weekly RSI) onto the bars of a
+/// lower timeframe (e.g. daily bars) so the two can be compared by index.
+///
+/// Each lower-timeframe bar `i` takes the value of the higher-timeframe bar
+/// it maps to, `higher_values[lower_index_to_higher[i]]`, so every higher
+/// value is forward-filled across its constituent lower bars.
+///
+/// NOTE: A higher-timeframe bar's value is only final once the bar closes.
+/// Mapping a daily bar to the week it belongs to therefore uses data from
+/// later in the week. To avoid that lookahead in a backtest, map each lower
+/// bar to the last *completed* higher bar instead.
+///
+/// # Arguments
+/// * `lower_index_to_higher` - `&[usize]` with the index into `higher_values` of each lower bar
+/// * `higher_values` - `&[f32]` containing the higher-timeframe values
+///
+/// ### Example
+/// ```
+/// ta::project_higher_tf(&day_to_week, &rsi::run(weekly_closes, 14));
+/// ```
+pub fn project_higher_tf(lower_index_to_higher: &[usize], higher_values: &[f32]) -> Vec<f32> {
+    let mut projected: Vec<f32> = Vec::new();
+    for (i, &h) in lower_index_to_higher.iter().enumerate() {
+        let value = match higher_values.get(h) {
+            Some(&v) => v,
+            None => panic!("Higher timeframe index {} of lower bar {} out of bounds. `higher_values` contains {} entries.", h, i, higher_values.len()),
+        };
+        projected.push(value);
+    }
+    return projected;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_project_higher_tf_weekly_onto_daily() {
+        // Two full weeks and a partial third week of daily bars
+        let day_to_week = vec![0, 0, 0, 0, 0, 1, 1, 1, 1, 1, 2, 2];
+        let weekly_rsi = vec![45.0, 55.0, 60.0];
+        assert_eq!(
+            project_higher_tf(&day_to_week, &weekly_rsi),
+            vec![45.0, 45.0, 45.0, 45.0, 45.0, 55.0, 55.0, 55.0, 55.0, 55.0, 60.0, 60.0]
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Higher timeframe index 2 of lower bar 3 out of bounds. `higher_values` contains 2 entries.")]
+    fn test_project_higher_tf_out_of_bounds() {
+        project_higher_tf(&[0, 0, 1, 2], &[45.0, 55.0]);
+    }
+}
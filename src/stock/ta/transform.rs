@@ -0,0 +1,185 @@
+/// Rounding applied to the values emitted by an indicator.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum Rounding {
+    /// Keep full precision.
+    None,
+    /// Round to the given number of decimals (see `round_to`).
+    Round(u32),
+    /// Truncate to the given number of decimals (see `truncate_to`).
+    Truncate(u32),
+}
+
+impl Default for Rounding {
+    fn default() -> Self { return Rounding::None; }
+}
+
+impl Rounding {
+    /// Apply the rounding to every entry of `values` in place.
+    pub fn apply(&self, values: &mut [f32]) {
+        match *self {
+            Rounding::None => {},
+            Rounding::Round(decimals) => round_to(values, decimals),
+            Rounding::Truncate(decimals) => truncate_to(values, decimals),
+        }
+    }
+}
+
+/// Clamp every entry of a `&mut [f32]` of indicator values into
+/// `[lower, upper]` in place.
+///
+/// Oscillators such as the CCI or a z-score can produce extreme outliers
+/// that dominate any downstream scaling (e.g. model features). Clamping them
+/// keeps the outliers' direction while limiting their magnitude.
+///
+/// NOTE: `NaN` entries are left untouched.
+///
+/// # Arguments
+/// * `values` - `&mut [f32]` containing indicator values to clamp
+/// * `lower` - Smallest allowed value
+/// * `upper` - Largest allowed value
+///
+/// ### Example
+/// ```
+/// transform::winsorize(&mut ccis, -300.0, 300.0);
+/// ```
+pub fn winsorize(values: &mut [f32], lower: f32, upper: f32) {
+    if lower > upper { panic!("Invalid bounds. `lower` ({}) must not be greater than `upper` ({}).", lower, upper); }
+    for v in values.iter_mut() {
+        if *v < lower {
+            *v = lower;
+        } else if *v > upper {
+            *v = upper;
+        }
+    }
+}
+
+/// Round every entry of a `&mut [f32]` of indicator values to `decimals`
+/// decimal places in place.
+///
+/// Indicator outputs are only reproducible up to the `f32` rounding of each
+/// operation, so the exact trailing digits can differ when the order of the
+/// calculation changes. Rounding to a documented precision (e.g. 2 decimals
+/// for display or comparisons) removes that noise.
+///
+/// NOTE: Halfway values are rounded away from zero. `NaN` entries are left
+/// untouched.
+///
+/// # Arguments
+/// * `values` - `&mut [f32]` containing indicator values to round
+/// * `decimals` - Number of decimal places to keep
+///
+/// ### Example
+/// ```
+/// transform::round_to(&mut rsis, 2);
+/// ```
+pub fn round_to(values: &mut [f32], decimals: u32) {
+    let factor = 10f64.powi(decimals as i32);
+    for v in values.iter_mut() {
+        *v = ((*v as f64 * factor).round() / factor) as f32;
+    }
+}
+
+/// Truncate every entry of a `&mut [f32]` of indicator values to
+/// `decimals` decimal places in place (rounding towards zero), as some
+/// charting platforms do for display.
+///
+/// NOTE: `NaN` entries are left untouched.
+///
+/// # Arguments
+/// * `values` - `&mut [f32]` containing indicator values to truncate
+/// * `decimals` - Number of decimal places to keep
+///
+/// ### Example
+/// ```
+/// transform::truncate_to(&mut rsis, 2);
+/// ```
+pub fn truncate_to(values: &mut [f32], decimals: u32) {
+    let factor = 10f64.powi(decimals as i32);
+    for v in values.iter_mut() {
+        *v = ((*v as f64 * factor).trunc() / factor) as f32;
+    }
+}
+
+/// Calculate the bar-to-bar change (first difference) of a `&[f32]` of
+/// indicator values.
+///
+/// ### Formula
+/// `delta[i] = values[i+1] - values[i]`
+///
+/// ### Usage
+/// Measures how fast a series is moving, e.g. the velocity of the RSI. The
+/// delta of the delta is the series' acceleration.
+///
+/// NOTE: The first value corresponds to `values[1]`, so the output contains
+/// one entry fewer than `values`.
+///
+/// # Arguments
+/// * `values` - `&[f32]` containing indicator values
+///
+/// ### Example
+/// ```
+/// ta::delta(&rsis);
+/// ```
+pub fn delta(values: &[f32]) -> Vec<f32> {
+    let mut deltas: Vec<f32> = Vec::new();
+    for i in 1..values.len() {
+        deltas.push(values[i] - values[i-1]);
+    }
+    return deltas;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_winsorize() {
+        let mut values = vec![-500.0, -50.0, 0.0, 120.0, 900.0];
+        winsorize(&mut values, -100.0, 100.0);
+        assert_eq!(values, vec![-100.0, -50.0, 0.0, 100.0, 100.0]);
+    }
+
+    #[test]
+    fn test_round_to() {
+        let mut values = vec![57.69231, 48.267326, 49.52316, -51.125, 0.004];
+        round_to(&mut values, 2);
+        assert_eq!(values, vec![57.69, 48.27, 49.52, -51.13, 0.0]);
+    }
+
+    #[test]
+    fn test_round_to_zero_decimals() {
+        let mut values = vec![57.69231, 48.5];
+        round_to(&mut values, 0);
+        assert_eq!(values, vec![58.0, 49.0]);
+    }
+
+    #[test]
+    fn test_truncate_to() {
+        let mut values = vec![57.69831, 48.267326, -51.129, 0.004];
+        truncate_to(&mut values, 2);
+        assert_eq!(values, vec![57.69, 48.26, -51.12, 0.0]);
+    }
+
+    #[test]
+    fn test_delta_monotonic() {
+        let values = vec![10.0, 11.0, 13.0, 16.0, 20.0];
+        let deltas = delta(&values);
+        assert_eq!(deltas, vec![1.0, 2.0, 3.0, 4.0]);
+        assert_eq!(deltas.iter().all(|&d| d > 0.0), true);
+        // Acceleration
+        assert_eq!(delta(&deltas), vec![1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_delta_short() {
+        assert_eq!(delta(&[10.0]), Vec::<f32>::new());
+        assert_eq!(delta(&[]), Vec::<f32>::new());
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid bounds. `lower` (10) must not be greater than `upper` (5).")]
+    fn test_winsorize_invalid_bounds() {
+        winsorize(&mut [1.0], 10.0, 5.0);
+    }
+}
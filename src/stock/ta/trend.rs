@@ -0,0 +1,86 @@
+use crate::stock::ta::linreg;
+
+/// Slope (as a percent of the average price per bar) below which a trend is `Flat`.
+const FLAT_SLOPE_PCT: f32 = 0.05;
+/// R-squared below which the price action is too noisy to call a trend.
+const MIN_R_SQUARED: f32 = 0.3;
+/// R-squared from which a trend is considered strong.
+const STRONG_R_SQUARED: f32 = 0.7;
+
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum TrendClass {
+    StrongUp,
+    WeakUp,
+    Flat,
+    WeakDown,
+    StrongDown,
+}
+
+/// Label the trend of every `period`-bar window of a `&[f32]` of price data.
+///
+/// Combines the slope and R-squared of a rolling linear regression (see
+/// `linreg::run`):
+/// - `Flat` if the R-squared is below 0.3 (noise) or the slope is below
+///   0.05% of the window's average price per bar
+/// - `StrongUp`/`StrongDown` if the R-squared is at least 0.7
+/// - `WeakUp`/`WeakDown` otherwise
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `period` - Number of prices in each regression window
+///
+/// # Returns
+/// `Vec<TrendClass>` where the first value corresponds to `prices[period - 1]`.
+///
+/// ### Example
+/// ```
+/// ta::classify_trend(&prices, 20);
+/// ```
+pub fn classify_trend(prices: &[f32], period: usize) -> Vec<TrendClass> {
+    let regressions = linreg::run(prices.to_vec(), period);
+    let mut classes: Vec<TrendClass> = Vec::new();
+    for (i, r) in regressions.iter().enumerate() {
+        let window = &prices[i..i+period];
+        let avg = window.iter().sum::<f32>() / period as f32;
+        let slope_pct = if avg == 0.0 { 0.0 } else { (r.slope / avg.abs()) * 100.0 };
+        let class = if r.r_squared < MIN_R_SQUARED || slope_pct.abs() < FLAT_SLOPE_PCT {
+            TrendClass::Flat
+        } else if r.r_squared >= STRONG_R_SQUARED {
+            if slope_pct > 0.0 { TrendClass::StrongUp } else { TrendClass::StrongDown }
+        } else {
+            if slope_pct > 0.0 { TrendClass::WeakUp } else { TrendClass::WeakDown }
+        };
+        classes.push(class);
+    }
+    return classes;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_trend_ramp() {
+        let prices: Vec<f32> = (0..30).map(|i| 100.0 + i as f32).collect();
+        let classes = classify_trend(&prices, 10);
+        assert_eq!(classes.len(), 21);
+        assert_eq!(classes.iter().all(|&c| c == TrendClass::StrongUp), true);
+
+        let prices: Vec<f32> = (0..30).map(|i| 100.0 - i as f32).collect();
+        assert_eq!(classify_trend(&prices, 10).iter().all(|&c| c == TrendClass::StrongDown), true);
+    }
+
+    #[test]
+    fn test_classify_trend_noise() {
+        let prices = vec![100.0, 101.0, 99.0, 100.5, 99.5, 100.0, 101.0, 99.0, 100.5, 99.5, 100.0, 101.0];
+        assert_eq!(classify_trend(&prices, 10), vec![TrendClass::Flat, TrendClass::Flat, TrendClass::Flat]);
+    }
+
+    #[test]
+    fn test_classify_trend_weak() {
+        // Rising, but with large swings around the trend
+        let prices = vec![100.0, 104.0, 99.0, 105.0, 101.0, 106.0, 102.0, 108.0, 103.0, 109.0];
+        assert_eq!(classify_trend(&prices, 10), vec![TrendClass::WeakUp]);
+    }
+}
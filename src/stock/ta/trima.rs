@@ -0,0 +1,93 @@
+use crate::stock::ta::sma;
+
+/// Calculate the triangular moving average (TRIMA) of a `Vec<f32>` of price data.
+///
+/// ### Definition
+/// Average price of an asset sampled over a given period of time, smoothed
+/// twice. Taking the SMA of an SMA weights the prices in the middle of the
+/// window most heavily, producing a smoother (but slower) line than a
+/// single SMA of the same length.
+///
+/// ### Formula
+/// `trima = sma(sma(prices, n1), n2)`
+///
+/// - odd `p`: `n1 = n2 = (p + 1) / 2`
+/// - even `p`: `n1 = p / 2` and `n2 = (p / 2) + 1`
+/// - `p`: number of periods being averaged
+///
+/// NOTE: `n1 + n2 - 1 = p`, so the TRIMA requires the same number of
+/// entries as an SMA over `p` periods and returns the same number of values.
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `periods` - Number of periods to average
+///
+/// ### Example
+/// ```
+/// trima::run(prices, 20);
+/// trima::run(prices, 50);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/t/triangularaverage.asp
+pub fn run(prices: Vec<f32>, periods: usize) -> Vec<f32> {
+    if periods == 0 || prices.len() < periods { panic!("Not enough entries to calculate the TRIMA. Received {}, but required {}.", prices.len(), periods); }
+    let (n1, n2) = if periods.is_multiple_of(2) {
+        (periods / 2, (periods / 2) + 1)
+    } else {
+        (periods.div_ceil(2), periods.div_ceil(2))
+    };
+    let smas = sma::run(prices, n1);
+    return sma::run(smas, n2);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variance(data: &[f32]) -> f32 {
+        let mean = data.iter().sum::<f32>() / data.len() as f32;
+        return data.iter().map(|d| (d - mean) * (d - mean)).sum::<f32>() / data.len() as f32;
+    }
+
+    fn changes(data: &[f32]) -> Vec<f32> {
+        return data.windows(2).map(|w| w[1] - w[0]).collect();
+    }
+
+    #[test]
+    fn test_run_odd() {
+        // sma(3) = [20, 30, 40], sma(3) = [30]
+        let prices = vec![10.0, 20.0, 30.0, 40.0, 50.0];
+        assert_eq!(run(prices, 5), vec![30.0]);
+    }
+
+    #[test]
+    fn test_run_even() {
+        // sma(2) = [15, 25, 35], sma(3) = [25]
+        let prices = vec![10.0, 20.0, 30.0, 40.0];
+        assert_eq!(run(prices, 4), vec![25.0]);
+    }
+
+    #[test]
+    fn test_run_smoother_than_sma() {
+        let prices = vec![
+            6.0, 13.0, 13.0, 8.0, 10.0,
+            9.0, 12.0, 13.0, 6.0, 5.0,
+            13.0, 9.0, 13.0, 5.0, 9.0,
+            12.0, 7.0, 14.0, 14.0, 5.0,
+            5.0, 10.0, 14.0, 9.0, 7.0,
+            9.0, 5.0, 7.0, 9.0, 10.0,
+        ];
+        let trimas = run(prices.to_vec(), 6);
+        let smas = sma::run(prices.to_vec(), 6);
+        assert_eq!(trimas.len(), smas.len());
+        // Bar-to-bar changes of the TRIMA vary less than those of the SMA
+        assert_eq!(variance(&changes(&trimas)) < variance(&changes(&smas)), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the TRIMA. Received 1, but required 5.")]
+    fn test_run_not_enough_elements() {
+        run(vec![10.0], 5);
+    }
+}
@@ -0,0 +1,363 @@
+use std::collections::VecDeque;
+
+/// Precondition failure found by `check_input`.
+#[derive(Debug)]
+#[derive(Clone, PartialEq)]
+pub enum TaError {
+    /// Fewer entries than the indicator needs.
+    NotEnoughEntries { received: usize, required: usize },
+    /// `NaN` or infinite entry at `index`.
+    NonFinite { index: usize, value: f32 },
+    /// Zero or negative price at `index`, rejected by `InputPolicy::Strict`.
+    NonPositive { index: usize, value: f32 },
+    /// Period the indicator cannot be calculated with, e.g. zero.
+    InvalidPeriod { received: usize, required: usize },
+}
+
+impl std::fmt::Display for TaError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            TaError::NotEnoughEntries { received, required } => write!(f, "Not enough entries. Received {}, but required {}.", received, required),
+            TaError::NonFinite { index, value } => write!(f, "Invalid entry at index {}. Received {}, but required a finite value.", index, value),
+            TaError::NonPositive { index, value } => write!(f, "Invalid price at index {}. Received {}, but required a positive price.", index, value),
+            TaError::InvalidPeriod { received, required } => write!(f, "Invalid period. Received {}, but required at least {}.", received, required),
+        }
+    }
+}
+
+/// Check the preconditions shared by every indicator: at least `min_len`
+/// entries, all of them finite.
+///
+/// A single `NaN` silently poisons every later value of a recursive
+/// indicator (e.g. the RSI's Wilder smoothing), so bad data is rejected up
+/// front instead.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `min_len` - Number of entries the indicator requires
+///
+/// ### Example
+/// ```
+/// util::check_input(&prices, period+1)?;
+/// ```
+pub fn check_input(prices: &[f32], min_len: usize) -> Result<(), TaError> {
+    if prices.len() < min_len { return Err(TaError::NotEnoughEntries { received: prices.len(), required: min_len }); }
+    for (i, &p) in prices.iter().enumerate() {
+        if !p.is_finite() { return Err(TaError::NonFinite { index: i, value: p }); }
+    }
+    return Ok(());
+}
+
+/// `check_input` for `(close, low, high)` bars: at least `min_len` bars,
+/// with every field finite.
+///
+/// NOTE: A non-finite field is reported at the index of its bar.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `min_len` - Number of bars the indicator requires
+///
+/// ### Example
+/// ```
+/// util::check_bars(&prices, period)?;
+/// ```
+pub fn check_bars(prices: &[(f32, f32, f32)], min_len: usize) -> Result<(), TaError> {
+    if prices.len() < min_len { return Err(TaError::NotEnoughEntries { received: prices.len(), required: min_len }); }
+    for (i, p) in prices.iter().enumerate() {
+        for value in [p.0, p.1, p.2] {
+            if !value.is_finite() { return Err(TaError::NonFinite { index: i, value: value }); }
+        }
+    }
+    return Ok(());
+}
+
+/// How `apply_policy` treats zero or negative prices, which can only come
+/// from bad data but make indicators such as log returns or the RSI
+/// silently produce nonsense.
+#[derive(Debug)]
+#[derive(Clone, Copy, PartialEq)]
+pub enum InputPolicy {
+    /// Reject the input with `TaError::NonPositive`.
+    Strict,
+    /// Clamp the price up to `MIN_PRICE`.
+    Lenient,
+}
+
+impl Default for InputPolicy {
+    fn default() -> Self { return InputPolicy::Strict; }
+}
+
+/// Price that `InputPolicy::Lenient` clamps zero or negative prices to.
+pub const MIN_PRICE: f32 = 1e-6;
+
+/// Apply an `InputPolicy` to a `&[f32]` of prices.
+///
+/// # Arguments
+/// * `prices` - `&[f32]` containing prices for a period of time
+/// * `policy` - Treatment of zero or negative prices
+///
+/// # Returns
+/// The prices, with any clamping applied, or the first rejected price.
+///
+/// ### Example
+/// ```
+/// let prices = util::apply_policy(&prices, InputPolicy::Strict)?;
+/// ```
+pub fn apply_policy(prices: &[f32], policy: InputPolicy) -> Result<Vec<f32>, TaError> {
+    let mut checked: Vec<f32> = Vec::new();
+    for (i, &p) in prices.iter().enumerate() {
+        if p > 0.0 || p.is_nan() {
+            checked.push(p);
+            continue;
+        }
+        match policy {
+            InputPolicy::Strict => return Err(TaError::NonPositive { index: i, value: p }),
+            InputPolicy::Lenient => checked.push(MIN_PRICE),
+        };
+    }
+    return Ok(checked);
+}
+
+/// Running total of the latest `period` values, updated in O(1) per value.
+///
+/// Indicators built on window sums (e.g. the SMA) would otherwise re-add
+/// the whole window at every bar, which is O(n * period).
+///
+/// NOTE: The total is kept in `f64` so that adding and removing values
+/// does not accumulate noticeable rounding error over long series.
+///
+/// ### Example
+/// ```
+/// let mut sum = util::RollingSum::new(20);
+/// for price in prices {
+///     sum.push(price);
+///     if sum.is_full() { sum.sum() / 20.0; }
+/// }
+/// ```
+pub struct RollingSum {
+    period: usize,
+    window: VecDeque<f32>,
+    total: f64,
+}
+
+impl RollingSum {
+    pub fn new(period: usize) -> Self {
+        if period == 0 { panic!("Invalid period for rolling sum. Received 0, but required at least 1."); }
+        return Self {
+            period: period,
+            window: VecDeque::with_capacity(period + 1),
+            total: 0.0,
+        };
+    }
+
+    /// Add the next value, dropping the oldest one once the window is full.
+    pub fn push(&mut self, value: f32) {
+        self.window.push_back(value);
+        self.total += value as f64;
+        if self.window.len() > self.period {
+            if let Some(v) = self.window.pop_front() { self.total -= v as f64; }
+        }
+    }
+
+    /// Sum of the values currently in the window.
+    pub fn sum(&self) -> f32 { return self.total as f32; }
+
+    /// Number of values currently in the window.
+    pub fn len(&self) -> usize { return self.window.len(); }
+
+    /// Whether the window holds no values.
+    pub fn is_empty(&self) -> bool { return self.window.is_empty(); }
+
+    /// Whether the window holds `period` values.
+    pub fn is_full(&self) -> bool { return self.window.len() == self.period; }
+}
+
+/// Maximum of the latest `period` values, updated in amortized O(1) per value.
+///
+/// Keeps a monotonic deque of the values that can still become the maximum:
+/// a new value evicts every smaller value before it, so the front of the
+/// deque is always the maximum of the window.
+///
+/// NOTE: Works on any `PartialOrd + Copy` value (e.g. `f32` prices or `i64`
+/// cents).
+///
+/// ### Example
+/// ```
+/// let mut high = util::RollingMax::new(14);
+/// for bar in prices {
+///     high.push(bar.2);
+///     high.value();
+/// }
+/// ```
+pub struct RollingMax<T = f32> {
+    window: Monotonic<T>,
+}
+
+impl<T: PartialOrd + Copy> RollingMax<T> {
+    pub fn new(period: usize) -> Self { return Self { window: Monotonic::new(period, |new, old| new >= old) }; }
+
+    /// Add the next value, dropping the oldest one once the window is full.
+    pub fn push(&mut self, value: T) { self.window.push(value); }
+
+    /// Maximum of the values currently in the window, if any.
+    pub fn value(&self) -> Option<T> { return self.window.front(); }
+}
+
+/// Minimum of the latest `period` values, updated in amortized O(1) per value.
+///
+/// See `RollingMax`.
+pub struct RollingMin<T = f32> {
+    window: Monotonic<T>,
+}
+
+impl<T: PartialOrd + Copy> RollingMin<T> {
+    pub fn new(period: usize) -> Self { return Self { window: Monotonic::new(period, |new, old| new <= old) }; }
+
+    /// Add the next value, dropping the oldest one once the window is full.
+    pub fn push(&mut self, value: T) { self.window.push(value); }
+
+    /// Minimum of the values currently in the window, if any.
+    pub fn value(&self) -> Option<T> { return self.window.front(); }
+}
+
+/// Monotonic deque of `(index, value)` shared by `RollingMax` and `RollingMin`.
+struct Monotonic<T> {
+    period: usize,
+    next_index: usize,
+    deque: VecDeque<(usize, T)>,
+    /// Whether a new value makes an older value irrelevant.
+    evicts: fn(T, T) -> bool,
+}
+
+impl<T: PartialOrd + Copy> Monotonic<T> {
+    fn new(period: usize, evicts: fn(T, T) -> bool) -> Self {
+        if period == 0 { panic!("Invalid period for rolling extreme. Received 0, but required at least 1."); }
+        return Self {
+            period: period,
+            next_index: 0,
+            deque: VecDeque::new(),
+            evicts: evicts,
+        };
+    }
+
+    fn push(&mut self, value: T) {
+        while let Some(&(_, back)) = self.deque.back() {
+            if !(self.evicts)(value, back) { break; }
+            self.deque.pop_back();
+        }
+        self.deque.push_back((self.next_index, value));
+        self.next_index += 1;
+        // Drop the front once it has slid out of the window
+        while let Some(&(i, _)) = self.deque.front() {
+            if i + self.period > self.next_index - 1 { break; }
+            self.deque.pop_front();
+        }
+    }
+
+    fn front(&self) -> Option<T> {
+        return self.deque.front().map(|&(_, v)| v);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_input() {
+        assert_eq!(check_input(&[1.0, 2.0, 3.0], 3), Ok(()));
+    }
+
+    #[test]
+    fn test_check_input_too_short() {
+        assert_eq!(check_input(&[1.0, 2.0], 3), Err(TaError::NotEnoughEntries { received: 2, required: 3 }));
+    }
+
+    #[test]
+    fn test_check_input_nan() {
+        let result = check_input(&[1.0, f32::NAN, 3.0], 3);
+        assert_eq!(result.is_err(), true);
+        assert_eq!(result.unwrap_err().to_string(), "Invalid entry at index 1. Received NaN, but required a finite value.");
+    }
+
+    #[test]
+    fn test_check_input_infinite() {
+        assert_eq!(check_input(&[1.0, 2.0, f32::INFINITY], 3), Err(TaError::NonFinite { index: 2, value: f32::INFINITY }));
+        assert_eq!(check_input(&[f32::NEG_INFINITY], 1), Err(TaError::NonFinite { index: 0, value: f32::NEG_INFINITY }));
+    }
+
+    #[test]
+    fn test_check_bars() {
+        assert_eq!(check_bars(&[(15.0, 10.0, 20.0), (18.0, 13.0, 22.0)], 2), Ok(()));
+        assert_eq!(check_bars(&[(15.0, 10.0, 20.0)], 2), Err(TaError::NotEnoughEntries { received: 1, required: 2 }));
+        assert_eq!(check_bars(&[(15.0, 10.0, 20.0), (18.0, f32::NEG_INFINITY, 22.0)], 2), Err(TaError::NonFinite { index: 1, value: f32::NEG_INFINITY }));
+    }
+
+    #[test]
+    fn test_apply_policy_strict() {
+        assert_eq!(apply_policy(&[10.0, 11.0], InputPolicy::Strict), Ok(vec![10.0, 11.0]));
+        let result = apply_policy(&[10.0, -2.0, 0.0], InputPolicy::Strict);
+        assert_eq!(result, Err(TaError::NonPositive { index: 1, value: -2.0 }));
+        assert_eq!(result.unwrap_err().to_string(), "Invalid price at index 1. Received -2, but required a positive price.");
+    }
+
+    #[test]
+    fn test_apply_policy_lenient() {
+        assert_eq!(apply_policy(&[10.0, -2.0, 0.0], InputPolicy::Lenient), Ok(vec![10.0, MIN_PRICE, MIN_PRICE]));
+    }
+
+    #[test]
+    fn test_rolling_sum() {
+        let mut sum = RollingSum::new(3);
+        let mut sums: Vec<f32> = Vec::new();
+        for v in [1.0, 2.0, 3.0, 4.0, 10.0] {
+            sum.push(v);
+            sums.push(sum.sum());
+        }
+        assert_eq!(sums, vec![1.0, 3.0, 6.0, 9.0, 17.0]);
+        assert_eq!(sum.len(), 3);
+        assert_eq!(sum.is_full(), true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid period for rolling sum. Received 0, but required at least 1.")]
+    fn test_rolling_sum_zero_period() {
+        RollingSum::new(0);
+    }
+
+    #[test]
+    fn test_rolling_max_min() {
+        let values = vec![5.0, 10.0, 11.0, 6.0, 5.0, 42.0, 33.0, 1.0, 1.0, 2.0];
+        let mut max = RollingMax::new(3);
+        let mut min = RollingMin::new(3);
+        let mut maxes: Vec<f32> = Vec::new();
+        let mut mins: Vec<f32> = Vec::new();
+        for &v in values.iter() {
+            max.push(v);
+            min.push(v);
+            maxes.push(max.value().unwrap());
+            mins.push(min.value().unwrap());
+        }
+        assert_eq!(maxes, vec![5.0, 10.0, 11.0, 11.0, 11.0, 42.0, 42.0, 42.0, 33.0, 2.0]);
+        assert_eq!(mins, vec![5.0, 5.0, 5.0, 6.0, 5.0, 5.0, 5.0, 1.0, 1.0, 1.0]);
+    }
+
+    #[test]
+    fn test_rolling_max_empty() {
+        assert_eq!(RollingMax::<f32>::new(3).value(), None);
+    }
+
+    #[test]
+    fn test_rolling_max_min_i64() {
+        let mut max: RollingMax<i64> = RollingMax::new(2);
+        let mut min: RollingMin<i64> = RollingMin::new(2);
+        let mut extremes: Vec<(i64, i64)> = Vec::new();
+        for v in [1015, 1020, 1008, 1012] {
+            max.push(v);
+            min.push(v);
+            extremes.push((max.value().unwrap_or(0), min.value().unwrap_or(0)));
+        }
+        assert_eq!(extremes, vec![(1015, 1015), (1020, 1015), (1020, 1008), (1012, 1008)]);
+    }
+}
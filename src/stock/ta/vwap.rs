@@ -0,0 +1,283 @@
+use crate::stock::ta::Indicator;
+
+/// Calculate the volume-weighted average price (VWAP) based on a
+/// `&[(f32, f32, f32)]` of price data and a `&[u64]` of volume data.
+///
+/// ### Definition
+/// Average price of an asset weighted by the volume traded at each price,
+/// accumulated from the start of the series. Shows whether the average
+/// participant is in profit at the current price.
+///
+/// ### Formula
+/// `tp = (h + l + c) / 3`
+///
+/// `vwap = sum(tp * v) / sum(v)`
+///
+/// - `tp`: typical price of a bar
+/// - `v`: volume of a bar
+///
+/// NOTE: Until any volume has traded the VWAP is undefined, so the typical
+/// price of the bar is emitted instead.
+///
+/// ### Usage
+/// Institutions often use the VWAP as a benchmark for their fills: buying
+/// below the VWAP and selling above it. Price trading above the VWAP is
+/// considered bullish and below it bearish.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `volumes` - `&[u64]` containing volume data for a period of time
+///
+/// ### Example
+/// ```
+/// vwap::run(&prices, &volumes);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/v/vwap.asp
+pub fn run(prices: &[(f32, f32, f32)], volumes: &[u64]) -> Vec<f32> {
+    return anchored(prices, volumes, 0);
+}
+
+/// Calculate the anchored VWAP, accumulating from a chosen bar (e.g. an
+/// earnings date or a swing low) rather than from the start of the series.
+///
+/// NOTE: Bars before the anchor have no value and are left out, so the
+/// first value corresponds to `prices[anchor_index]`.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `volumes` - `&[u64]` containing volume data for a period of time
+/// * `anchor_index` - Index of the bar to start accumulating from
+///
+/// ### Example
+/// ```
+/// vwap::anchored(&prices, &volumes, earnings_index);
+/// ```
+pub fn anchored(prices: &[(f32, f32, f32)], volumes: &[u64], anchor_index: usize) -> Vec<f32> {
+    if prices.len() != volumes.len() { panic!("Length mismatch. `prices` contains {} entries, but `volumes` contains {}.", prices.len(), volumes.len()); }
+    if anchor_index >= prices.len() { panic!("Anchor index {} out of bounds. `prices` contains {} entries.", anchor_index, prices.len()); }
+    let mut vwaps: Vec<f32> = Vec::new();
+    let mut cum_pv: f64 = 0.0;
+    let mut cum_v: f64 = 0.0;
+    for i in anchor_index..prices.len() {
+        let (close, low, high) = prices[i];
+        let tp = (high + low + close) / 3.0;
+        cum_pv += tp as f64 * volumes[i] as f64;
+        cum_v += volumes[i] as f64;
+        if cum_v == 0.0 {
+            vwaps.push(tp);
+        } else {
+            vwaps.push((cum_pv / cum_v) as f32);
+        }
+    }
+    return vwaps;
+}
+
+/// Calculate the VWAP along with standard deviation bands around it.
+///
+/// ### Formula
+/// `var = (sum(tp^2 * v) / sum(v)) - vwap^2`
+///
+/// `upper = vwap + (m * sqrt(var))`
+///
+/// `lower = vwap - (m * sqrt(var))`
+///
+/// - `var`: volume-weighted variance of the typical price
+/// - `m`: multiplier (commonly 1, 2 or 3)
+///
+/// ### Usage
+/// Intraday traders fade moves to the outer bands back towards the VWAP, or
+/// treat a close beyond a band as a sign of trend strength.
+///
+/// # Arguments
+/// * `prices` - `&[(f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(close, low, high)]`
+/// * `volumes` - `&[u64]` containing volume data for a period of time
+/// * `multiplier` - Number of standard deviations from the VWAP to each band
+///
+/// # Returns
+/// `Vec<(f32, f32, f32)>` in the format of `(lower, vwap, upper)`.
+///
+/// ### Example
+/// ```
+/// vwap::with_bands(&prices, &volumes, 2.0);
+/// ```
+pub fn with_bands(prices: &[(f32, f32, f32)], volumes: &[u64], multiplier: f32) -> Vec<(f32, f32, f32)> {
+    if prices.len() != volumes.len() { panic!("Length mismatch. `prices` contains {} entries, but `volumes` contains {}.", prices.len(), volumes.len()); }
+    let mut bands: Vec<(f32, f32, f32)> = Vec::new();
+    let mut cum_pv: f64 = 0.0;
+    let mut cum_p2v: f64 = 0.0;
+    let mut cum_v: f64 = 0.0;
+    for i in 0..prices.len() {
+        let (close, low, high) = prices[i];
+        let tp = (high + low + close) / 3.0;
+        cum_pv += tp as f64 * volumes[i] as f64;
+        cum_p2v += (tp as f64) * (tp as f64) * volumes[i] as f64;
+        cum_v += volumes[i] as f64;
+        if cum_v == 0.0 {
+            bands.push((tp, tp, tp));
+        } else {
+            let vwap = cum_pv / cum_v;
+            // Rounding can leave a tiny negative variance when all prices are equal
+            let std_dev = ((cum_p2v / cum_v) - (vwap * vwap)).max(0.0).sqrt();
+            let width = multiplier as f64 * std_dev;
+            bands.push(((vwap - width) as f32, vwap as f32, (vwap + width) as f32));
+        }
+    }
+    return bands;
+}
+
+/// Calculate the VWAP for intraday data, resetting the accumulation at the
+/// start of every session (e.g. each trading day).
+///
+/// Each bar belongs to the session starting at `timestamp - (timestamp % session_secs)`.
+/// Without the reset, an intraday VWAP would keep accumulating across days
+/// and drift away from the current session's prices.
+///
+/// # Arguments
+/// * `bars` - `&[(u64, f32, f32, f32)]` containing prices for a period of time
+///   in the format of `&[(timestamp, close, low, high)]` with the timestamp in seconds
+/// * `volumes` - `&[u64]` containing volume data for a period of time
+/// * `session_secs` - Length of a session in seconds
+///
+/// ### Example
+/// ```
+/// vwap::sessioned(&bars, &volumes, 24 * 60 * 60);
+/// ```
+pub fn sessioned(bars: &[(u64, f32, f32, f32)], volumes: &[u64], session_secs: u64) -> Vec<f32> {
+    if bars.len() != volumes.len() { panic!("Length mismatch. `bars` contains {} entries, but `volumes` contains {}.", bars.len(), volumes.len()); }
+    if session_secs == 0 { panic!("Invalid session length. Received 0, but required at least 1 second."); }
+    let mut vwaps: Vec<f32> = Vec::new();
+    let mut session: Option<u64> = None;
+    let mut cum_pv: f64 = 0.0;
+    let mut cum_v: f64 = 0.0;
+    for i in 0..bars.len() {
+        let (ts, close, low, high) = bars[i];
+        let start = ts - (ts % session_secs);
+        if session != Some(start) {
+            session = Some(start);
+            cum_pv = 0.0;
+            cum_v = 0.0;
+        }
+        let tp = (high + low + close) / 3.0;
+        cum_pv += tp as f64 * volumes[i] as f64;
+        cum_v += volumes[i] as f64;
+        if cum_v == 0.0 {
+            vwaps.push(tp);
+        } else {
+            vwaps.push((cum_pv / cum_v) as f32);
+        }
+    }
+    return vwaps;
+}
+
+/// `Indicator` implementation of the volume-weighted average price's `run`.
+pub struct Vwap {}
+
+impl Indicator for Vwap {
+    type Input = (Vec<(f32, f32, f32)>, Vec<u64>);
+    type Output = Vec<f32>;
+
+    fn compute(&self, data: &Self::Input) -> Self::Output { return run(&data.0, &data.1); }
+    fn warmup(&self) -> usize { return 0; }
+    fn requires_volume(&self) -> bool { return true; }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        let prices = vec![(10.0, 9.0, 11.0), (13.0, 11.0, 12.0), (12.0, 11.0, 13.0)];
+        assert_eq!(run(&prices, &[100, 300, 100]), vec![10.0, 11.5, 11.6]);
+    }
+
+    #[test]
+    fn test_anchored_from_start() {
+        let prices = vec![
+            (15.0, 10.0, 20.0), (18.0, 13.0, 22.0),
+            (18.0, 10.0, 19.0), (21.0, 13.0, 22.0),
+            (12.0, 10.0, 32.0), (14.0, 13.0, 27.0),
+        ];
+        let volumes = vec![100, 500, 300, 1500, 200, 800];
+        assert_eq!(anchored(&prices, &volumes, 0), run(&prices, &volumes));
+    }
+
+    #[test]
+    fn test_anchored_later_bar() {
+        let prices = vec![(10.0, 9.0, 11.0), (13.0, 11.0, 12.0), (12.0, 11.0, 13.0)];
+        let vwaps = anchored(&prices, &[100, 300, 100], 1);
+        assert_eq!(vwaps, vec![12.0, 12.0]);
+    }
+
+    #[test]
+    fn test_run_no_volume() {
+        let prices = vec![(10.0, 9.0, 11.0), (13.0, 11.0, 12.0)];
+        assert_eq!(run(&prices, &[0, 100]), vec![10.0, 12.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `prices` contains 2 entries, but `volumes` contains 1.")]
+    fn test_run_length_mismatch() {
+        run(&[(10.0, 9.0, 11.0), (13.0, 11.0, 12.0)], &[100]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Anchor index 3 out of bounds. `prices` contains 2 entries.")]
+    fn test_anchored_out_of_bounds() {
+        anchored(&[(10.0, 9.0, 11.0), (13.0, 11.0, 12.0)], &[100, 100], 3);
+    }
+
+    #[test]
+    fn test_with_bands() {
+        let prices = vec![(10.0, 9.0, 11.0), (13.0, 11.0, 12.0), (12.0, 11.0, 13.0)];
+        let volumes = vec![100, 300, 100];
+        let bands = with_bands(&prices, &volumes, 1.0);
+        let vwaps: Vec<f32> = bands.iter().map(|b| b.1).collect();
+        assert_eq!(vwaps, run(&prices, &volumes));
+        // Only one price has traded so far
+        assert_eq!(bands[0], (10.0, 10.0, 10.0));
+        // tp = [10, 12, 12] weighted [1, 3, 1]: var = 0.64
+        assert_eq!(bands[2], (10.8, 11.6, 12.4));
+    }
+
+    #[test]
+    fn test_with_bands_multiplier() {
+        let prices = vec![(10.0, 9.0, 11.0), (13.0, 11.0, 12.0), (12.0, 11.0, 13.0)];
+        let volumes = vec![100, 300, 100];
+        let narrow = with_bands(&prices, &volumes, 1.0);
+        let wide = with_bands(&prices, &volumes, 2.0);
+        for i in 1..narrow.len() {
+            let narrow_width = narrow[i].2 - narrow[i].0;
+            let wide_width = wide[i].2 - wide[i].0;
+            assert_eq!(wide_width > narrow_width, true);
+            assert_eq!((wide_width - 2.0 * narrow_width).abs() < 1e-4, true);
+        }
+    }
+
+    #[test]
+    fn test_sessioned_resets() {
+        const DAY: u64 = 24 * 60 * 60;
+        let bars = vec![
+            (DAY + 60, 10.0, 9.0, 11.0), (DAY + 120, 13.0, 11.0, 12.0),
+            (2 * DAY + 60, 20.0, 19.0, 21.0), (2 * DAY + 120, 23.0, 21.0, 22.0),
+        ];
+        let volumes = vec![100, 300, 100, 300];
+        let vwaps = sessioned(&bars, &volumes, DAY);
+        assert_eq!(vwaps, vec![10.0, 11.5, 20.0, 21.5]);
+
+        // Without the reset the second session is dragged down by the first
+        let prices: Vec<(f32, f32, f32)> = bars.iter().map(|b| (b.1, b.2, b.3)).collect();
+        assert_eq!(run(&prices, &volumes)[2] < vwaps[2], true);
+    }
+
+    #[test]
+    #[should_panic(expected = "Invalid session length. Received 0, but required at least 1 second.")]
+    fn test_sessioned_zero_session() {
+        sessioned(&[(0, 10.0, 9.0, 11.0)], &[100], 0);
+    }
+}
@@ -0,0 +1,71 @@
+use crate::stock::ta::util::{RollingMax, RollingMin};
+
+/// Calculate the Williams %R based on a `Vec<(f32, f32, f32)>` of price data.
+///
+/// ### Definition
+/// A momentum indicator -- bounded between [-100-0] -- measuring where the
+/// close sits relative to the highest high of the lookback window. It uses
+/// the same window as the stochastic oscillator, inverted.
+///
+/// ### Formula
+/// `%R = ((h14 - c) / (h14 - l14)) * -100`
+///
+/// - `c`: current price
+/// - `h14`: highest price during last 14 (`period`) trading sessions
+/// - `l14`: lowest price during last 14 (`period`) trading sessions
+///
+/// NOTE: The first value corresponds to `prices[period - 1]`.
+///
+/// ### Usage
+/// Readings above -20 are considered overbought and readings below -80
+/// oversold. Like the stochastic oscillator, a reading can stay overbought
+/// or oversold for a long time in a strong trend.
+///
+/// # Arguments
+/// * `prices` - `Vec<(f32, f32, f32)>` containing prices for a period of time
+///   in the format of `Vec<(close, low, high)>`
+/// * `period` - Number of trading sessions in the high/low window (commonly 14)
+///
+/// ### Example
+/// ```
+/// williams_r::run(prices, 14);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/terms/w/williamsr.asp
+pub fn run(prices: Vec<(f32, f32, f32)>, period: usize) -> Vec<f32> {
+    if period == 0 { panic!("Invalid period for Williams %R. Received 0, but required at least 1."); }
+    if prices.len() < period { panic!("Not enough entries to calculate the Williams %R. Received {}, but required {}.", prices.len(), period); }
+    let mut rs: Vec<f32> = Vec::new();
+    let mut highs = RollingMax::new(period);
+    let mut lows = RollingMin::new(period);
+    for (i, &(close, low, high)) in prices.iter().enumerate() {
+        highs.push(high);
+        lows.push(low);
+        if i < period - 1 { continue; }
+        let (low14, high14) = match (lows.value(), highs.value()) {
+            (Some(l), Some(h)) => (l, h),
+            _ => panic!("Could not get entry in `prices`."),
+        };
+        rs.push(((high14 - close) / (high14 - low14)) * -100.0);
+    }
+    return rs;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        let prices = vec![(15.0, 10.0, 20.0), (18.0, 13.0, 22.0)];
+        // ((22 - 18) / (22 - 10)) * -100
+        assert_eq!(run(prices, 2), vec![-33.333336]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the Williams %R. Received 1, but required 2.")]
+    fn test_run_not_enough_elements() {
+        run(vec![(15.0, 10.0, 20.0)], 2);
+    }
+}
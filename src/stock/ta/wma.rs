@@ -0,0 +1,62 @@
+/// Calculate the weighted moving average (WMA) of a `Vec<f32>` of price data.
+///
+/// ### Definition
+/// Average price of an asset sampled over a given period of time where each
+/// price is weighted by its position in the window, so the most recent
+/// price counts the most and the weights decrease linearly.
+///
+/// ### Formula
+/// `wma = ((p_n * n) + (p_n-1 * (n - 1)) + ... + (p_1 * 1)) / (n * (n + 1) / 2)`
+///
+/// - `p_n`: most recent price in the window
+/// - `n`: number of periods to average
+///
+/// # Arguments
+/// * `prices` - `Vec<f32>` containing prices for a period of time
+/// * `periods` - Number of periods to average
+///
+/// ### Example
+/// ```
+/// wma::run(prices, 10);
+/// wma::run(prices, 20);
+/// ```
+///
+/// #### Resources
+/// - https://www.investopedia.com/articles/technical/060401.asp
+pub fn run(prices: Vec<f32>, periods: usize) -> Vec<f32> {
+    if periods == 0 || prices.len() < periods { panic!("Not enough entries to calculate the WMA. Received {}, but required {}.", prices.len(), periods); }
+    let denominator = (periods * (periods + 1)) as f32 / 2.0;
+    let mut wmas: Vec<f32> = Vec::new();
+    for i in periods..prices.len()+1 {
+        let mut sum = 0.0;
+        for (j, p) in prices[i-periods..i].iter().enumerate() {
+            sum += p * (j + 1) as f32;
+        }
+        wmas.push(sum / denominator);
+    }
+    return wmas;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_run_simple() {
+        // ((10 * 1) + (20 * 2) + (30 * 3)) / 6
+        let prices = vec![10.0, 20.0, 30.0];
+        assert_eq!(run(prices, 3), vec![23.333334]);
+    }
+
+    #[test]
+    fn test_run_complex() {
+        let prices = vec![10.0, 20.0, 30.0, 30.0, 0.0];
+        assert_eq!(run(prices, 3), vec![23.333334, 28.333334, 15.0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Not enough entries to calculate the WMA. Received 1, but required 5.")]
+    fn test_run_not_enough_elements() {
+        run(vec![10.0], 5);
+    }
+}
@@ -0,0 +1,120 @@
+/// Generate a reproducible synthetic price series following a geometric
+/// Brownian motion, for exercising indicators on realistic inputs.
+///
+/// ### Formula
+/// `p_t = p_t-1 * e^((drift - vol^2 / 2) + vol * z)`
+///
+/// - `z`: standard normal random number
+/// - `drift`: expected log return per bar (e.g. `0.0005`)
+/// - `vol`: standard deviation of the log return per bar (e.g. `0.01`)
+///
+/// NOTE: The random numbers come from a small built-in generator
+/// (SplitMix64 with a Box-Muller transform), so the same `seed` always
+/// yields the same series on every platform and run.
+///
+/// # Arguments
+/// * `seed` - Seed of the random number generator
+/// * `n` - Number of prices to generate (including `start`)
+/// * `drift` - Expected log return per bar
+/// * `vol` - Volatility of the log return per bar
+/// * `start` - First price of the series
+///
+/// ### Example
+/// ```
+/// rsi::run(testutil::geometric_brownian(42, 1000, 0.0005, 0.01, 100.0), 14);
+/// ```
+pub fn geometric_brownian(seed: u64, n: usize, drift: f32, vol: f32, start: f32) -> Vec<f32> {
+    let mut state = seed;
+    let mut prices: Vec<f32> = Vec::new();
+    if n == 0 { return prices; }
+    let mut price = start as f64;
+    prices.push(start);
+    for _ in 1..n {
+        // Box-Muller: two uniforms in (0, 1] to a standard normal
+        let u1 = 1.0 - uniform(&mut state);
+        let u2 = uniform(&mut state);
+        let z = (-2.0 * u1.ln()).sqrt() * (2.0 * std::f64::consts::PI * u2).cos();
+        let (drift, vol) = (drift as f64, vol as f64);
+        price *= ((drift - vol * vol / 2.0) + vol * z).exp();
+        prices.push(price as f32);
+    }
+    return prices;
+}
+
+/// Uniform random number in [0, 1) using SplitMix64.
+fn uniform(state: &mut u64) -> f64 {
+    *state = state.wrapping_add(0x9E3779B97F4A7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z = z ^ (z >> 31);
+    return (z >> 11) as f64 / (1u64 << 53) as f64;
+}
+
+/// Assert two `&[f32]` series are equal within `epsilon` at every index.
+///
+/// Exact `assert_eq!` comparisons of `f32` outputs break whenever a
+/// legitimate refactor changes the order of a calculation (e.g. switching
+/// to an iterator sum), so reference values are compared within a
+/// tolerance instead.
+///
+/// ### Example
+/// ```
+/// assert_close(&rsi::run(prices, 14), &vec![57.69231], 1e-4);
+/// ```
+pub fn assert_close(a: &[f32], b: &[f32], epsilon: f32) {
+    if a.len() != b.len() { panic!("Length mismatch. `a` contains {} entries, but `b` contains {}.\n  a: {:?}\n  b: {:?}", a.len(), b.len(), a, b); }
+    for i in 0..a.len() {
+        let diff = (a[i] - b[i]).abs();
+        if diff.is_nan() || diff > epsilon {
+            panic!("Values differ at index {}: {} and {} are not within {}.\n  a: {:?}\n  b: {:?}", i, a[i], b[i], epsilon, a, b);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_assert_close() {
+        assert_close(&[1.0, 2.00001], &[1.00001, 2.0], 1e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Values differ at index 1: 2.1 and 2 are not within 0.0001.")]
+    fn test_assert_close_differs() {
+        assert_close(&[1.0, 2.1], &[1.0, 2.0], 1e-4);
+    }
+
+    #[test]
+    #[should_panic(expected = "Length mismatch. `a` contains 1 entries, but `b` contains 2.")]
+    fn test_assert_close_length_mismatch() {
+        assert_close(&[1.0], &[1.0, 2.0], 1e-4);
+    }
+
+    #[test]
+    fn test_geometric_brownian_reproducible() {
+        let a = geometric_brownian(42, 500, 0.0005, 0.01, 100.0);
+        let b = geometric_brownian(42, 500, 0.0005, 0.01, 100.0);
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 500);
+        assert_eq!(a[0], 100.0);
+        assert_eq!(a.iter().all(|&p| p > 0.0), true);
+        assert_ne!(geometric_brownian(43, 500, 0.0005, 0.01, 100.0), a);
+    }
+
+    #[test]
+    fn test_geometric_brownian_volatility() {
+        let prices = geometric_brownian(7, 2000, 0.0, 0.02, 100.0);
+        let returns: Vec<f32> = prices.windows(2).map(|w| (w[1] / w[0]).ln()).collect();
+        let mean = returns.iter().sum::<f32>() / returns.len() as f32;
+        let var = returns.iter().map(|r| (r - mean) * (r - mean)).sum::<f32>() / returns.len() as f32;
+        assert_eq!((var.sqrt() - 0.02).abs() < 0.002, true);
+    }
+
+    #[test]
+    fn test_geometric_brownian_empty() {
+        assert_eq!(geometric_brownian(42, 0, 0.0, 0.01, 100.0), Vec::<f32>::new());
+    }
+}